@@ -0,0 +1,186 @@
+//! Integer literals in the syntax of assemblers, e.g. `0FFh` in Intel,
+//! `$FF` in Motorola or `0xFF` in AT&T syntax.
+
+use crate::parse::ParseOptions;
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// An assembler's convention for integer literals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AsmSyntax {
+    /// Intel/MASM: a radix suffix, i.e. `h` for hex, `o` or `q` for octal,
+    /// `b` or `y` for binary and an optional `d` or `t` for decimal. A
+    /// literal starts with a decimal digit, so hex literals such as `0BEh`
+    /// are padded with a `0`.
+    Intel,
+    /// Motorola/6502: a radix prefix, i.e. `$FF`, `%1010` and `@17`.
+    Motorola,
+    /// AT&T/GAS: C-like `0xFF`, `0b1010` and `017`, optionally marked as an
+    /// immediate with a leading `$`.
+    Att,
+}
+
+impl AsmSyntax {
+    /// Write `num` as a literal, in the radix it is stored in, which must
+    /// be 2, 8, 10 or 16.
+    pub fn print(self, num: &RadixNum) -> RadixResult<String> {
+        let (radix, digits) = (num.radix(), num.as_str());
+        Ok(match (self, radix) {
+            (_, 10) => digits.to_string(),
+            (AsmSyntax::Intel, 16)
+                if !digits.starts_with(|c: char| c.is_ascii_digit()) =>
+                format!("0{}h", digits),
+            (AsmSyntax::Intel, 16) => format!("{}h", digits),
+            (AsmSyntax::Intel, 8) => format!("{}o", digits),
+            (AsmSyntax::Intel, 2) => format!("{}b", digits),
+            (AsmSyntax::Motorola, 16) => format!("${}", digits),
+            (AsmSyntax::Motorola, 8) => format!("@{}", digits),
+            (AsmSyntax::Motorola, 2) => format!("%{}", digits),
+            (AsmSyntax::Att, 16) => format!("0x{}", digits),
+            (AsmSyntax::Att, 8) => format!("0{}", digits),
+            (AsmSyntax::Att, 2) => format!("0b{}", digits),
+            _ => return Err(RadixErr::RadixNotSupported(radix)),
+        })
+    }
+
+    /// Parse a literal into a `RadixNum` in the radix of the literal.
+    pub fn parse(self, literal: &str) -> RadixResult<RadixNum> {
+        let input: &str = literal.trim();
+        let first: char = input.chars().next().ok_or(RadixErr::EmptyInput)?;
+        let (digits, radix): (&str, usize) = match self {
+            AsmSyntax::Intel => {
+                // Only the last character selects the radix, so `0BEh` is
+                // hex even though `B` is also the binary suffix.
+                if !first.is_ascii_digit() {
+                    return Err(RadixErr::IllegalChar(first));
+                }
+                let last: char = input.chars().last().unwrap_or(first);
+                let body: &str = &input[.. input.len() - last.len_utf8()];
+                match last.to_ascii_lowercase() {
+                    'h' => (strip_hex_pad(body), 16),
+                    'o' | 'q' => (body, 8),
+                    'b' | 'y' => (body, 2),
+                    't' | 'd' => (body, 10),
+                    _ => (input, 10),
+                }
+            },
+            AsmSyntax::Motorola => match first {
+                '$' => (&input[1 ..], 16),
+                '@' => (&input[1 ..], 8),
+                '%' => (&input[1 ..], 2),
+                _ => (input, 10),
+            },
+            AsmSyntax::Att => {
+                let input: &str = input.strip_prefix('$').unwrap_or(input);
+                let lower: String = input.to_ascii_lowercase();
+                if lower.starts_with("0x") {
+                    (&input[2 ..], 16)
+                } else if lower.starts_with("0b") {
+                    (&input[2 ..], 2)
+                } else if input.len() > 1 && input.starts_with('0') {
+                    (&input[1 ..], 8)
+                } else {
+                    (input, 10)
+                }
+            },
+        };
+        RadixNum::from_str_in(literal, digits, radix, &ParseOptions::new())
+    }
+}
+
+/// Strip the `0` that Intel syntax requires before a leading letter, e.g.
+/// in `0BEh`, so that it doesn't count toward the width.
+pub(crate) fn strip_hex_pad(digits: &str) -> &str {
+    match digits.strip_prefix('0') {
+        Some(rest) if rest.starts_with(|c: char| c.is_ascii_alphabetic()) =>
+            rest,
+        _ => digits,
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::asm::*;
+
+    #[test]
+    fn intel() -> RadixResult<()> {
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   AsmSyntax::Intel.parse("0FFh")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   AsmSyntax::Intel.parse("0FFH")?);
+        assert_eq!(RadixNum::from_str("BE", 16)?,
+                   AsmSyntax::Intel.parse("0BEh")?);
+        assert_eq!(RadixNum::from_str("10B", 16)?,
+                   AsmSyntax::Intel.parse("10Bh")?);
+        assert_eq!(RadixNum::from_str("1010", 2)?,
+                   AsmSyntax::Intel.parse("1010b")?);
+        assert_eq!(RadixNum::from_str("17", 8)?,
+                   AsmSyntax::Intel.parse("17q")?);
+        assert_eq!(RadixNum::from_str("42", 10)?,
+                   AsmSyntax::Intel.parse("42")?);
+        assert_eq!(Err(RadixErr::IllegalChar('F')),
+                   AsmSyntax::Intel.parse("FFh"));
+        assert!(AsmSyntax::Intel.parse("0BEb").is_err());
+        assert_eq!("0FFh",
+                   AsmSyntax::Intel.print(&RadixNum::from_str("FF", 16)?)?);
+        assert_eq!("1Fh",
+                   AsmSyntax::Intel.print(&RadixNum::from_str("1F", 16)?)?);
+        assert_eq!("1010b",
+                   AsmSyntax::Intel.print(&RadixNum::from_str("1010", 2)?)?);
+        Ok(())
+    }
+
+    #[test]
+    fn motorola() -> RadixResult<()> {
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   AsmSyntax::Motorola.parse("$FF")?);
+        assert_eq!(RadixNum::from_str("1010", 2)?,
+                   AsmSyntax::Motorola.parse("%1010")?);
+        assert_eq!(RadixNum::from_str("17", 8)?,
+                   AsmSyntax::Motorola.parse("@17")?);
+        assert_eq!("$FF",
+                   AsmSyntax::Motorola.print(&RadixNum::from_str("FF", 16)?)?);
+        assert_eq!("%1010",
+                   AsmSyntax::Motorola.print(&RadixNum::from_str("1010", 2)?)?);
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "$1G".to_string(),
+            position: 2,
+            digits: vec!['G'],
+            radix: 16,
+        }), AsmSyntax::Motorola.parse("$1G"));
+        Ok(())
+    }
+
+    #[test]
+    fn att() -> RadixResult<()> {
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   AsmSyntax::Att.parse("$0xFF")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   AsmSyntax::Att.parse("0xff")?);
+        assert_eq!(RadixNum::from_str("17", 8)?,
+                   AsmSyntax::Att.parse("017")?);
+        assert_eq!(RadixNum::from_str("0", 10)?,    AsmSyntax::Att.parse("0")?);
+        assert_eq!("00",
+                   AsmSyntax::Att.print(&RadixNum::from_str("0", 8)?)?);
+        assert_eq!("0xFF",
+                   AsmSyntax::Att.print(&RadixNum::from_str("FF", 16)?)?);
+        assert_eq!(Err(RadixErr::RadixNotSupported(36)),
+                   AsmSyntax::Att.print(&RadixNum::from_str("Z", 36)?));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let syntaxes = [AsmSyntax::Intel, AsmSyntax::Motorola, AsmSyntax::Att];
+        for &syntax in syntaxes.iter() {
+            for &radix in &[2, 8, 10, 16] {
+                for &value in &[0_u32, 7, 0xBE, 0xDEAD] {
+                    let num = RadixNum::from(value).with_radix(radix)?;
+                    assert_eq!(num, syntax.parse(&syntax.print(&num)?)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
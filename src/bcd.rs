@@ -0,0 +1,253 @@
+//! Binary-coded decimal: packed and unpacked BCD, Excess-3 and Aiken 2421
+//! codes, and IBM style signed packed decimals.
+
+use crate::{RadixErr, RadixResult};
+use std::convert::TryFrom;
+
+/// The ways in which a decimal digit can be encoded in a nibble.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DigitCode {
+    /// Plain BCD, where each nibble is the binary value of the digit.
+    Bcd8421,
+    /// Each nibble is the binary value of the digit plus 3.
+    Excess3,
+    /// The self-complementing 2421 code, e.g. `5` is `1011`.
+    Aiken2421,
+}
+
+const AIKEN_2421: [u8; 10] = [
+    0b0000, 0b0001, 0b0010, 0b0011, 0b0100,
+    0b1011, 0b1100, 0b1101, 0b1110, 0b1111,
+];
+
+impl DigitCode {
+    /// Encode a decimal `digit` in `0..=9` as a nibble.
+    pub fn encode(self, digit: u8) -> RadixResult<u8> {
+        if digit > 9 { return Err(RadixErr::IllegalDigit(digit as usize)); }
+        Ok(match self {
+            DigitCode::Bcd8421 => digit,
+            DigitCode::Excess3 => digit + 3,
+            DigitCode::Aiken2421 => AIKEN_2421[digit as usize],
+        })
+    }
+
+    /// Decode a `nibble` to a decimal digit.
+    pub fn decode(self, nibble: u8) -> RadixResult<u8> {
+        let digit: Option<u8> = match self {
+            DigitCode::Bcd8421 if nibble <= 9 => Some(nibble),
+            DigitCode::Excess3 if (3 ..= 12).contains(&nibble) =>
+                Some(nibble - 3),
+            DigitCode::Aiken2421 => AIKEN_2421.iter()
+                .position(|&code| code == nibble)
+                .map(|digit| digit as u8),
+            _ => None,
+        };
+        digit.ok_or(RadixErr::IllegalDigit(nibble as usize))
+    }
+}
+
+/// The sign nibbles of IBM packed decimals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Sign {
+    /// `C`
+    Positive,
+    /// `D`
+    Negative,
+    /// `F`
+    Unsigned,
+}
+
+impl Sign {
+    pub fn nibble(self) -> u8 {
+        match self {
+            Sign::Positive => 0xC,
+            Sign::Negative => 0xD,
+            Sign::Unsigned => 0xF,
+        }
+    }
+
+    pub fn from_nibble(nibble: u8) -> RadixResult<Self> {
+        match nibble {
+            0xC => Ok(Sign::Positive),
+            0xD => Ok(Sign::Negative),
+            0xF => Ok(Sign::Unsigned),
+            n => Err(RadixErr::IllegalDigit(n as usize)),
+        }
+    }
+}
+
+/// Encode `number` with two digits per byte, most significant first.
+/// An odd number of digits is padded with a leading zero digit.
+pub fn to_packed(number: u128, code: DigitCode) -> Vec<u8> {
+    let mut nibbles: Vec<u8> = nibbles(number, code);
+    if nibbles.len() % 2 == 1 { nibbles.insert(0, zero(code)); }
+    pack(&nibbles)
+}
+
+/// Decode a number that was encoded with two digits per byte.
+pub fn from_packed(bytes: &[u8], code: DigitCode) -> RadixResult<u128> {
+    if bytes.is_empty() { return Err(RadixErr::EmptyInput); }
+    let nibbles: Vec<u8> = unpack(bytes);
+    accumulate(nibbles.iter().map(|&nibble| code.decode(nibble)))
+}
+
+/// Encode `number` with one digit per byte, most significant first.
+/// The high nibble of each byte is zero.
+pub fn to_unpacked(number: u128, code: DigitCode) -> Vec<u8> {
+    nibbles(number, code)
+}
+
+/// Decode a number that was encoded with one digit per byte.
+pub fn from_unpacked(bytes: &[u8], code: DigitCode) -> RadixResult<u128> {
+    if bytes.is_empty() { return Err(RadixErr::EmptyInput); }
+    accumulate(bytes.iter().map(|&byte| match byte >> 4 {
+        0 => code.decode(byte),
+        _ => Err(RadixErr::IllegalDigit(byte as usize)),
+    }))
+}
+
+/// Encode `number` as a signed packed decimal, i.e. packed BCD followed
+/// by a `C` (positive) or `D` (negative) sign nibble.
+pub fn to_packed_decimal(number: i128) -> Vec<u8> {
+    let sign: Sign = if number < 0 { Sign::Negative } else { Sign::Positive };
+    packed_decimal(number.unsigned_abs(), sign)
+}
+
+/// Encode `number` as an unsigned packed decimal, i.e. packed BCD followed
+/// by an `F` sign nibble.
+pub fn to_packed_unsigned(number: u128) -> Vec<u8> {
+    packed_decimal(number, Sign::Unsigned)
+}
+
+/// Decode a packed decimal, with a `C`, `D` or `F` sign nibble.
+pub fn from_packed_decimal(bytes: &[u8]) -> RadixResult<i128> {
+    let (magnitude, sign) = from_packed_decimal_unsigned(bytes)?;
+    let overflow = RadixErr::ArithmeticOverflow;
+    match sign {
+        Sign::Negative => 0i128.checked_sub_unsigned(magnitude).ok_or(overflow),
+        _ => i128::try_from(magnitude).map_err(|_| overflow),
+    }
+}
+
+/// Decode a packed decimal into its magnitude and sign.
+pub fn from_packed_decimal_unsigned(bytes: &[u8]) -> RadixResult<(u128, Sign)> {
+    let nibbles: Vec<u8> = unpack(bytes);
+    let (&sign, digits) = nibbles.split_last().ok_or(RadixErr::EmptyInput)?;
+    let sign: Sign = Sign::from_nibble(sign)?;
+    let code = DigitCode::Bcd8421;
+    let magnitude: u128 =
+        accumulate(digits.iter().map(|&nibble| code.decode(nibble)))?;
+    Ok((magnitude, sign))
+}
+
+fn packed_decimal(magnitude: u128, sign: Sign) -> Vec<u8> {
+    let mut nibbles: Vec<u8> = nibbles(magnitude, DigitCode::Bcd8421);
+    nibbles.push(sign.nibble());
+    if nibbles.len() % 2 == 1 { nibbles.insert(0, 0); }
+    pack(&nibbles)
+}
+
+/// The code of each decimal digit of `number`, most significant first.
+fn nibbles(number: u128, code: DigitCode) -> Vec<u8> {
+    number.to_string().bytes()
+        .map(|digit| code.encode(digit - b'0').unwrap_or_default())
+        .collect()
+}
+
+#[inline(always)]
+fn zero(code: DigitCode) -> u8 {
+    code.encode(0).unwrap_or_default()
+}
+
+fn pack(nibbles: &[u8]) -> Vec<u8> {
+    nibbles.chunks(2).map(|pair| (pair[0] << 4) | pair[1]).collect()
+}
+
+fn unpack(bytes: &[u8]) -> Vec<u8> {
+    bytes.iter().flat_map(|&byte| vec![byte >> 4, byte & 0xF]).collect()
+}
+
+/// Interpret decimal `digits`, most significant first, as a number.
+fn accumulate(
+    digits: impl Iterator<Item=RadixResult<u8>>,
+) -> RadixResult<u128> {
+    let mut number: u128 = 0;
+    for digit in digits {
+        let digit: u128 = digit? as u128;
+        number = number.checked_mul(10)
+            .and_then(|number| number.checked_add(digit))
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(number)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::bcd::*;
+
+    #[test]
+    fn packed() -> RadixResult<()> {
+        assert_eq!(vec![0x12, 0x34], to_packed(1234, DigitCode::Bcd8421));
+        assert_eq!(vec![0x01, 0x23], to_packed( 123, DigitCode::Bcd8421));
+        assert_eq!(vec![0x34, 0x56], to_packed( 123, DigitCode::Excess3));
+        assert_eq!(vec![0xBF],       to_packed(  59, DigitCode::Aiken2421));
+        assert_eq!(59,   from_packed(&[0x59], DigitCode::Bcd8421)?);
+        assert_eq!(123,  from_packed(&[0x34, 0x56], DigitCode::Excess3)?);
+        assert_eq!(59,   from_packed(&[0xBF], DigitCode::Aiken2421)?);
+        assert_eq!(Err(RadixErr::IllegalDigit(0xA)),
+                   from_packed(&[0x1A], DigitCode::Bcd8421));
+        assert_eq!(Err(RadixErr::IllegalDigit(0x2)),
+                   from_packed(&[0x32], DigitCode::Excess3));
+        assert_eq!(Err(RadixErr::IllegalDigit(0x5)),
+                   from_packed(&[0x05], DigitCode::Aiken2421));
+        Ok(())
+    }
+
+    #[test]
+    fn unpacked() -> RadixResult<()> {
+        assert_eq!(vec![0x05, 0x09], to_unpacked(59, DigitCode::Bcd8421));
+        assert_eq!(vec![0x08, 0x0C], to_unpacked(59, DigitCode::Excess3));
+        assert_eq!(59, from_unpacked(&[0x05, 0x09], DigitCode::Bcd8421)?);
+        assert_eq!(Err(RadixErr::IllegalDigit(0x35)),
+                   from_unpacked(&[0x35], DigitCode::Bcd8421));
+        Ok(())
+    }
+
+    #[test]
+    fn packed_decimals() -> RadixResult<()> {
+        assert_eq!(vec![0x12, 0x3D],       to_packed_decimal(-123));
+        assert_eq!(vec![0x01, 0x23, 0x4C], to_packed_decimal(1234));
+        assert_eq!(vec![0x0C],             to_packed_decimal(0));
+        assert_eq!(vec![0x04, 0x2F],       to_packed_unsigned(42));
+        assert_eq!(-123, from_packed_decimal(&[0x12, 0x3D])?);
+        assert_eq!(1234, from_packed_decimal(&[0x01, 0x23, 0x4C])?);
+        assert_eq!(42,   from_packed_decimal(&[0x04, 0x2F])?);
+        assert_eq!((42, Sign::Unsigned),
+                   from_packed_decimal_unsigned(&[0x04, 0x2F])?);
+        assert_eq!(Err(RadixErr::IllegalDigit(0xA)),
+                   from_packed_decimal(&[0x12, 0x3A]));
+        assert_eq!(Err(RadixErr::IllegalDigit(0xC)),
+                   from_packed_decimal(&[0x1C, 0x3C]));
+        assert_eq!(Err(RadixErr::EmptyInput), from_packed_decimal(&[]));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let codes = [
+            DigitCode::Bcd8421, DigitCode::Excess3, DigitCode::Aiken2421
+        ];
+        for &code in codes.iter() {
+            for &n in &[0, 7, 10, 99, 12345, u64::MAX as u128, u128::MAX] {
+                assert_eq!(n, from_packed(&to_packed(n, code), code)?);
+                assert_eq!(n, from_unpacked(&to_unpacked(n, code), code)?);
+            }
+        }
+        for &n in &[i128::MIN, -1, 0, 1, i128::MAX] {
+            assert_eq!(n, from_packed_decimal(&to_packed_decimal(n))?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,315 @@
+//! Non-integer bases: the golden ratio base (phinary) and general greedy
+//! beta-expansions for any real base `1 < β <= 36`.
+
+use crate::{digit_to_char, RadixErr, RadixResult};
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The golden ratio `φ = (1 + √5) / 2`, the base of the phinary system.
+pub const PHI: f64 = 1.618_033_988_749_895;
+
+/// The most digits `beta_expansion` writes, far beyond the precision of an
+/// `f64`, which bounds the work for a base close to 1.
+pub const MAX_DIGITS: usize = 4096;
+
+/// A number written in a non-integer base. The digits use the same
+/// alphabet as `RadixNum`, with an optional sign and radix point.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BetaNum {
+    base: f64,
+    digits: String,
+}
+
+impl BetaNum {
+    pub fn as_str(&self) -> &str { &self.digits }
+
+    /// Retrieve the base that `self` is encoded with.
+    pub fn base(&self) -> f64 { self.base }
+
+    pub fn digits<'c>(&'c self) -> impl Iterator<Item=char> + 'c {
+        self.digits.chars()
+    }
+
+    /// Approximate the represented value.
+    pub fn value(&self) -> f64 {
+        let (negative, digits) = split_sign(&self.digits);
+        let int_len: usize = digits.find('.').unwrap_or(digits.len());
+        let value: f64 = digits.chars()
+            .filter(|&c| c != '.')
+            .enumerate()
+            .map(|(idx, c)| {
+                let exponent = int_len as i32 - 1 - idx as i32;
+                c.to_digit(36).unwrap_or(0) as f64 * self.base.powi(exponent)
+            })
+            .sum();
+        if negative { -value } else { value }
+    }
+}
+
+impl fmt::Display for BetaNum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Write the integer `number` in base φ, in standard form.
+pub fn phinary(number: i64) -> RadixResult<BetaNum> {
+    phinary_ratio(number, 1, usize::MAX)
+}
+
+/// Write `numer / denom` in base φ, in standard form. The expansion of a
+/// non-integer rational is infinite, so it is cut off after `frac_digits`
+/// digits following the radix point.
+pub fn phinary_ratio(
+    numer: i64,
+    denom: i64,
+    frac_digits: usize,
+) -> RadixResult<BetaNum> {
+    if denom == 0 { return Err(RadixErr::NotFinite); }
+    let negative: bool = (numer < 0) != (denom < 0);
+    let numer = Phi { a: (numer as i128).abs(), b: 0 };
+    let denom: i128 = (denom as i128).abs();
+    greedy_phinary(negative && numer != Phi::ZERO, numer, denom, frac_digits)
+}
+
+/// Rewrite a phinary digit string such as `"11.011"` in standard form,
+/// i.e. without adjacent `1` digits, e.g. `"100.1"`.
+pub fn normalize_phinary(digits: &str) -> RadixResult<BetaNum> {
+    let (negative, digits) = split_sign(digits.trim());
+    if digits.is_empty() { return Err(RadixErr::EmptyInput); }
+    if digits.matches('.').count() > 1 {
+        return Err(RadixErr::IllegalChar('.'));
+    }
+    let int_len: usize = digits.find('.').unwrap_or(digits.len());
+    let mut value: Phi = Phi::ZERO;
+    for (idx, c) in digits.chars().filter(|&c| c != '.').enumerate() {
+        let exponent: i32 = int_len as i32 - 1 - idx as i32;
+        match c {
+            '0' => {},
+            '1' => value = value.add(Phi::power(exponent)?)?,
+            c => return Err(RadixErr::IllegalChar(c)),
+        }
+    }
+    greedy_phinary(negative && value != Phi::ZERO, value, 1, usize::MAX)
+}
+
+/// Compute the greedy expansion of `x` in base `beta`, with `frac_digits`
+/// digits following the radix point. Digits lie in `0 .. ceil(beta)`.
+/// An expansion of more than `MAX_DIGITS` digits fails with
+/// `RadixErr::ArithmeticOverflow`.
+pub fn beta_expansion(
+    x: f64,
+    beta: f64,
+    frac_digits: usize,
+) -> RadixResult<BetaNum> {
+    if !x.is_finite() { return Err(RadixErr::NotFinite); }
+    if !(beta > 1.0 && beta <= 36.0) {
+        return Err(RadixErr::BaseNotSupported);
+    }
+    let max_digit: usize = beta.ceil() as usize - 1;
+    // The exponent of the leading digit, checked against the limit before
+    // it can overflow an `i32`.
+    let estimate: f64 = (x.abs().ln() / beta.ln()).floor().max(0.0);
+    if estimate + 1.0 + frac_digits as f64 > MAX_DIGITS as f64 {
+        return Err(RadixErr::ArithmeticOverflow);
+    }
+    let mut top: i32 = estimate as i32;
+    // Correct the rounding of the logarithm at exact powers of `beta`.
+    while top > 0 && beta.powi(top) > x.abs() { top -= 1; }
+    while beta.powi(top + 1) <= x.abs() { top += 1; }
+    if top as usize + 1 + frac_digits > MAX_DIGITS {
+        return Err(RadixErr::ArithmeticOverflow);
+    }
+
+    // Apply the beta-transformation `r -> β r mod 1` to `|x| / β^top`,
+    // taking the integer parts as the digits.
+    let mut rem: f64 = x.abs() / beta.powi(top);
+    let mut digits: String = String::new();
+    if x < 0.0 { digits.push('-'); }
+    let frac_digits: i32 = frac_digits as i32;
+    for exponent in (-frac_digits ..= top).rev() {
+        if exponent == -1 { digits.push('.'); }
+        let digit: usize = (rem.floor() as usize).min(max_digit);
+        rem = (rem - digit as f64).max(0.0) * beta;
+        digits.push(digit_to_char(digit)?);
+    }
+    Ok(BetaNum { base: beta, digits })
+}
+
+/// Greedily expand `numer / denom` in base φ.
+fn greedy_phinary(
+    negative: bool,
+    numer: Phi,
+    denom: i128,
+    frac_digits: usize,
+) -> RadixResult<BetaNum> {
+    let term = |exponent: i32| Phi::power(exponent)?.scale(denom);
+    let mut rem: Phi = numer;
+    let mut top: i32 = 0;
+    while rem.sub(term(top + 1)?)?.signum()? != Ordering::Less { top += 1; }
+
+    let mut digits: String = String::new();
+    if negative { digits.push('-'); }
+    let mut exponent: i32 = top;
+    loop {
+        let done = rem == Phi::ZERO || (-exponent) as usize > frac_digits;
+        if exponent < 0 && done { break; }
+        if exponent == -1 { digits.push('.'); }
+        let diff: Phi = rem.sub(term(exponent)?)?;
+        if diff.signum()? == Ordering::Less {
+            digits.push('0');
+        } else {
+            digits.push('1');
+            rem = diff;
+        }
+        exponent -= 1;
+    }
+    Ok(BetaNum { base: PHI, digits })
+}
+
+#[inline(always)]
+fn split_sign(digits: &str) -> (bool, &str) {
+    match digits.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, digits),
+    }
+}
+
+/// An element `a + bφ` of `Z[φ]`, which is closed under multiplication
+/// by `φ` and `1/φ`, so the phinary expansion can be computed exactly.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Phi { a: i128, b: i128 }
+
+impl Phi {
+    const ZERO: Phi = Phi { a: 0, b: 0 };
+
+    /// Compute `φ^exponent`, using `φ^k = F(k-1) + F(k)φ` and
+    /// `φ^-k = (-1)^k (F(k+1) - F(k)φ)`.
+    fn power(exponent: i32) -> RadixResult<Self> {
+        let k: u32 = exponent.unsigned_abs();
+        let overflow = || RadixErr::ArithmeticOverflow;
+        let (mut prev, mut fib): (i128, i128) = (1, 0); // F(-1), F(0)
+        for _ in 0 .. k {
+            let next: i128 = prev.checked_add(fib).ok_or_else(overflow)?;
+            prev = fib;
+            fib = next;
+        }
+        if exponent >= 0 { return Ok(Phi { a: prev, b: fib }); }
+        let next: i128 = prev.checked_add(fib).ok_or_else(overflow)?;
+        let sign: i128 = if k & 1 == 0 { 1 } else { -1 };
+        Ok(Phi { a: sign * next, b: -sign * fib })
+    }
+
+    fn add(self, other: Phi) -> RadixResult<Self> {
+        let a = self.a.checked_add(other.a);
+        let b = self.b.checked_add(other.b);
+        match (a, b) {
+            (Some(a), Some(b)) => Ok(Phi { a, b }),
+            _ => Err(RadixErr::ArithmeticOverflow),
+        }
+    }
+
+    fn sub(self, other: Phi) -> RadixResult<Self> {
+        self.add(other.scale(-1)?)
+    }
+
+    fn scale(self, factor: i128) -> RadixResult<Self> {
+        let a = self.a.checked_mul(factor);
+        let b = self.b.checked_mul(factor);
+        match (a, b) {
+            (Some(a), Some(b)) => Ok(Phi { a, b }),
+            _ => Err(RadixErr::ArithmeticOverflow),
+        }
+    }
+
+    /// Compare `self` to zero, using `a + bφ = (2a + b + b√5) / 2`.
+    fn signum(self) -> RadixResult<Ordering> {
+        let overflow = || RadixErr::ArithmeticOverflow;
+        let x: i128 = self.a.checked_mul(2)
+            .and_then(|a| a.checked_add(self.b))
+            .ok_or_else(overflow)?;
+        let y: i128 = self.b;
+        let square = |v: i128| v.unsigned_abs().checked_mul(v.unsigned_abs());
+        let x2: u128 = square(x).ok_or_else(overflow)?;
+        let y2: u128 = square(y)
+            .and_then(|v| v.checked_mul(5))
+            .ok_or_else(overflow)?;
+        Ok(match (x.cmp(&0), y.cmp(&0)) {
+            (Ordering::Equal, Ordering::Equal) => Ordering::Equal,
+            (Ordering::Less, Ordering::Less) |
+            (Ordering::Less, Ordering::Equal) |
+            (Ordering::Equal, Ordering::Less) => Ordering::Less,
+            (Ordering::Greater, Ordering::Less) => x2.cmp(&y2),
+            (Ordering::Less, Ordering::Greater) => y2.cmp(&x2),
+            _ => Ordering::Greater,
+        })
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::beta::*;
+
+    #[test]
+    fn phinary_integers() -> RadixResult<()> {
+        assert_eq!("0",          phinary(0)?.as_str());
+        assert_eq!("1",          phinary(1)?.as_str());
+        assert_eq!("10.01",      phinary(2)?.as_str());
+        assert_eq!("100.01",     phinary(3)?.as_str());
+        assert_eq!("101.01",     phinary(4)?.as_str());
+        assert_eq!("1000.1001",  phinary(5)?.as_str());
+        assert_eq!("10100.0101", phinary(10)?.as_str());
+        assert_eq!("-10.01",     phinary(-2)?.as_str());
+        for n in -100 .. 100 {
+            let num = phinary(n)?;
+            assert!(!num.as_str().contains("11"));
+            assert!((num.value() - n as f64).abs() < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn phinary_rationals() -> RadixResult<()> {
+        assert_eq!("0.010010010", phinary_ratio(1, 2, 9)?.as_str());
+        assert_eq!("-0.01001",    phinary_ratio(1, -2, 5)?.as_str());
+        assert_eq!("10.01",       phinary_ratio(6, 3, 9)?.as_str());
+        assert_eq!(Err(RadixErr::NotFinite), phinary_ratio(1, 0, 9));
+        Ok(())
+    }
+
+    #[test]
+    fn normalize() -> RadixResult<()> {
+        assert_eq!("100",    normalize_phinary("11")?.as_str());
+        assert_eq!("1",      normalize_phinary("0.11")?.as_str());
+        assert_eq!("100.1",  normalize_phinary("11.011")?.as_str());
+        assert_eq!("10.01",  normalize_phinary("1.11")?.as_str());
+        assert_eq!("-10.01", normalize_phinary("-1.11")?.as_str());
+        assert_eq!(Err(RadixErr::IllegalChar('2')), normalize_phinary("12"));
+        assert_eq!(Err(RadixErr::IllegalChar('.')), normalize_phinary("1.1.1"));
+        Ok(())
+    }
+
+    #[test]
+    fn beta_expansions() -> RadixResult<()> {
+        assert_eq!("12.375",  beta_expansion(12.375, 10.0, 3)?.as_str());
+        assert_eq!("101.1",   beta_expansion(5.5, 2.0, 1)?.as_str());
+        assert_eq!("-FF.8",   beta_expansion(-255.5, 16.0, 1)?.as_str());
+        assert_eq!("100.01",  beta_expansion(3.0, PHI, 2)?.as_str());
+        let num = beta_expansion(std::f64::consts::PI, 2.5, 20)?;
+        assert!((num.value() - std::f64::consts::PI).abs() < 1e-7);
+        assert!(num.digits().all(|c| c == '.' || ('0'..='2').contains(&c)));
+        let unsupported = Err(RadixErr::BaseNotSupported);
+        assert_eq!(unsupported, beta_expansion(1.0, 1.0, 3));
+        assert_eq!(unsupported, beta_expansion(1.0, 37.0, 3));
+        assert_eq!(Err(RadixErr::NotFinite), beta_expansion(f64::NAN, 2.0, 3));
+        assert_eq!("1000", beta_expansion(8.0, 2.0, 0)?.as_str());
+        assert_eq!("1000", beta_expansion(1000.0, 10.0, 0)?.as_str());
+        let overflow = Err(RadixErr::ArithmeticOverflow);
+        assert_eq!(overflow, beta_expansion(1e6, 1.0001, 0));
+        assert_eq!(overflow, beta_expansion(f64::MAX, 1.0 + 1e-15, 0));
+        assert_eq!(overflow, beta_expansion(1.0, 2.0, usize::MAX));
+        Ok(())
+    }
+}
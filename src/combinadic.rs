@@ -0,0 +1,166 @@
+//! The combinatorial number system (combinadic), which ranks the k-subsets
+//! of an n-element set `{0, 1, ..., n - 1}` by a single integer.
+
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// Compute the binomial coefficient `C(n, k)`, reporting overflow as
+/// `RadixErr::ArithmeticOverflow` rather than panicking.
+pub fn binomial(n: usize, k: usize) -> RadixResult<u128> {
+    if k > n { return Ok(0); }
+    let k: usize = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0 .. k {
+        // `result * (n - i) / (i + 1)` is exact; dividing out the gcd
+        // first keeps the intermediate product as small as possible.
+        let numerator: u128 = (n - i) as u128;
+        let denominator: u128 = (i + 1) as u128;
+        let g: u128 = gcd(result, denominator);
+        let (result_g, denominator_g) = (result / g, denominator / g);
+        result = (numerator / denominator_g)
+            .checked_mul(result_g)
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(result)
+}
+
+/// Compute the rank of the k-subset `subset` of `{0, 1, ..., n - 1}`.
+/// The elements may be given in any order, but must be distinct.
+pub fn encode(n: usize, subset: &[usize]) -> RadixResult<u128> {
+    let mut elements: Vec<usize> = subset.to_vec();
+    elements.sort_unstable();
+    for (idx, &element) in elements.iter().enumerate() {
+        let is_duplicate = idx > 0 && elements[idx - 1] == element;
+        if element >= n || is_duplicate {
+            return Err(RadixErr::InvalidSubset(element));
+        }
+    }
+    let mut rank: u128 = 0;
+    for (idx, &element) in elements.iter().enumerate() {
+        rank = rank
+            .checked_add(binomial(element, idx + 1)?)
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(rank)
+}
+
+/// Compute the k-subset of `{0, 1, ..., n - 1}` that has the given `rank`.
+/// The elements are returned in ascending order.
+pub fn decode(n: usize, k: usize, rank: u128) -> RadixResult<Vec<usize>> {
+    // A count of subsets that overflows is larger than any rank.
+    if binomial(n, k).is_ok_and(|count| rank >= count) {
+        return Err(RadixErr::ValueOutOfRange(rank));
+    }
+    let mut rank: u128 = rank;
+    let mut elements: Vec<usize> = Vec::with_capacity(k);
+    let mut upper: usize = n;
+    for i in (1 ..= k).rev() {
+        // Binary search for the largest `c < upper` such that
+        // `C(c, i) <= rank`, as `C(c, i)` grows with `c` and `C(i - 1, i)`
+        // is 0. An overflowing coefficient is certainly larger than `rank`.
+        let fits = |c: usize| binomial(c, i).is_ok_and(|b| b <= rank);
+        let (mut candidate, mut high): (usize, usize) = (i - 1, upper - 1);
+        while candidate < high {
+            let mid: usize = candidate + (high - candidate).div_ceil(2);
+            if fits(mid) { candidate = mid; } else { high = mid - 1; }
+        }
+        rank -= binomial(candidate, i)?;
+        elements.push(candidate);
+        upper = candidate;
+    }
+    elements.reverse();
+    Ok(elements)
+}
+
+/// Compute the rank of `subset` like `encode`, encoded in a certain `radix`.
+pub fn encode_radix(
+    n: usize,
+    subset: &[usize],
+    radix: usize,
+) -> RadixResult<RadixNum> {
+    RadixNum::from_decimal(encode(n, subset)?, radix)
+}
+
+/// Compute the k-subset whose rank is represented by `rank`, like `decode`.
+pub fn decode_radix(
+    n: usize,
+    k: usize,
+    rank: &RadixNum,
+) -> RadixResult<Vec<usize>> {
+    decode(n, k, rank.as_u128()?)
+}
+
+#[inline(always)]
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::combinadic::*;
+
+    #[test]
+    fn binomial_coefficients() {
+        assert_eq!(Ok(1),   binomial(0, 0));
+        assert_eq!(Ok(0),   binomial(3, 4));
+        assert_eq!(Ok(120), binomial(10, 3));
+        assert_eq!(Ok(120), binomial(10, 7));
+        assert_eq!(
+            Ok(100_891_344_545_564_193_334_812_497_256),
+            binomial(100, 50)
+        );
+        assert_eq!(Err(RadixErr::ArithmeticOverflow), binomial(200, 100));
+    }
+
+    #[test]
+    fn encode_subsets() {
+        assert_eq!(Ok(0),   encode(5, &[]));
+        assert_eq!(Ok(143), encode(11, &[2, 7, 10]));
+        assert_eq!(Ok(143), encode(11, &[10, 2, 7]));
+        assert_eq!(Ok(153), encode(10, &[1, 4, 6, 9]));
+        assert_eq!(Err(RadixErr::InvalidSubset(11)), encode(11, &[2, 11]));
+        assert_eq!(Err(RadixErr::InvalidSubset(2)),  encode(11, &[2, 2]));
+    }
+
+    #[test]
+    fn decode_ranks() {
+        assert_eq!(Ok(vec![2, 7, 10]),   decode(11, 3, 143));
+        assert_eq!(Ok(vec![1, 4, 6, 9]), decode(10, 4, 153));
+        assert_eq!(Ok(vec![]),           decode(5, 0, 0));
+        assert_eq!(Err(RadixErr::ValueOutOfRange(120)), decode(10, 3, 120));
+        assert_eq!(Ok(vec![198]), decode(200, 1, 198));
+        assert_eq!(Ok(vec![0]), decode(200_000_000, 1, 0));
+        let subset = vec![5, usize::MAX - 1];
+        let rank: u128 = encode(usize::MAX, &subset).expect("rank");
+        assert_eq!(Ok(subset), decode(usize::MAX, 2, rank));
+        let first: Vec<usize> = (0 .. 100).collect();
+        assert_eq!(Ok(first), decode(200, 100, 0));
+        let subset: Vec<usize> = decode(200, 100, 5).expect("subset");
+        assert_eq!(Ok(5), encode(200, &subset));
+        let subset: Vec<usize> = decode(200, 100, u128::MAX).expect("subset");
+        assert_eq!(Ok(u128::MAX), encode(200, &subset));
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let (n, k) = (9, 4);
+        for rank in 0 .. binomial(n, k)? {
+            let subset = decode(n, k, rank)?;
+            assert_eq!(k, subset.len());
+            assert_eq!(rank, encode(n, &subset)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn radix_rank() -> RadixResult<()> {
+        let rank = encode_radix(11, &[2, 7, 10], 16)?;
+        assert_eq!("8F", rank.as_str());
+        assert_eq!(vec![2, 7, 10], decode_radix(11, 3, &rank)?);
+        let subset: Vec<usize> = (60 .. 120).collect();
+        let rank = encode_radix(120, &subset, 16)?;
+        assert_eq!(subset, decode_radix(120, 60, &rank)?);
+        Ok(())
+    }
+}
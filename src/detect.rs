@@ -0,0 +1,239 @@
+//! Radix detection for values that arrive without a radix attached.
+
+use crate::asm::strip_hex_pad;
+use crate::parse::ParseOptions;
+use crate::{char_to_digit, MAX_RADIX};
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// The result of `RadixNum::parse_auto`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Detected {
+    /// The input was a literal that names its radix, e.g. `0xFF`.
+    Literal(RadixNum),
+    /// The input was a bare digit string, which may be read in each of
+    /// these radices, most likely first.
+    Candidates(Vec<Candidate>),
+}
+
+/// A reading of a bare digit string in a certain radix.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    num: RadixNum,
+    confidence: f64,
+}
+
+impl Candidate {
+    pub fn num(&self) -> &RadixNum { &self.num }
+
+    pub fn radix(&self) -> usize { self.num.radix() }
+
+    /// The confidence in this reading, in `0.0..=1.0`. The confidences of
+    /// all candidates add up to 1.
+    pub fn confidence(&self) -> f64 { self.confidence }
+}
+
+impl RadixNum {
+    /// Parse `input`, detecting its radix. Literals with a `0b`, `0o` or
+    /// `0x` prefix, a leading `0` for octal, an `rN` suffix, an Ada style
+    /// `N#...#` radix or an Intel style `h` suffix are read in that radix,
+    /// so that `Display` output parses back to the same number.
+    ///
+    /// A bare digit string yields candidate radices, ranked by the smallest
+    /// radix whose digits cover the string, how common a radix is, and
+    /// hints such as an even number of hex digits.
+    ///
+    /// A literal with invalid digits fails, unless its marker could be a
+    /// digit of a bare string, e.g. the `b` of `0beef` or an `h` suffix.
+    /// Then the string is read as bare.
+    pub fn parse_auto(input: &str) -> RadixResult<Detected> {
+        let trimmed: &str = input.trim();
+        if trimmed.is_empty() { return Err(RadixErr::EmptyInput); }
+        match parse_literal(input) {
+            Some(Ok(num)) => Ok(Detected::Literal(num)),
+            Some(Err(err)) if may_be_bare(trimmed) => candidates(input)
+                .map(Detected::Candidates)
+                .map_err(|_| err),
+            Some(Err(err)) => Err(err),
+            None => candidates(input).map(Detected::Candidates),
+        }
+    }
+}
+
+/// Whether the marker of a literal could also be a digit, i.e. the `0b` and
+/// `0o` prefixes and the `h` suffix, so that `input` may be a bare string.
+fn may_be_bare(input: &str) -> bool {
+    input.starts_with("0b") || input.starts_with("0o") || input.ends_with('h')
+}
+
+/// Parse `input` if it is a literal that names its radix, e.g. `0xFF`.
+pub(crate) fn parse_literal(input: &str) -> Option<RadixResult<RadixNum>> {
+    literal(input.trim()).map(|(digits, radix)| {
+        RadixNum::from_str_in(input, digits, radix, &ParseOptions::new())
+    })
+}
+
+/// Split a literal that names its radix into its digits and radix.
+fn literal(input: &str) -> Option<(&str, usize)> {
+    let is_decimal = |s: &str| !s.is_empty()
+        && s.chars().all(|c| c.is_ascii_digit());
+    let radix = |s: &str| s.parse().ok()
+        .filter(|&radix| RadixNum::validate_radix(radix).is_ok());
+    if let Some(digits) = input.strip_prefix("0x")
+        .or_else(|| input.strip_prefix("0X")) {
+        return Some((digits, 16));
+    }
+    if let Some(digits) = input.strip_prefix("0o") {
+        return Some((digits, 8));
+    }
+    if let Some(digits) = input.strip_prefix("0b") {
+        return Some((digits, 2));
+    }
+    if let Some((digits, suffix)) = input.rsplit_once('r') {
+        if !digits.is_empty() && is_decimal(suffix) {
+            return Some((digits, radix(suffix)?));
+        }
+    }
+    if let Some((prefix, rest)) = input.split_once('#') {
+        if is_decimal(prefix) {
+            return Some((rest.strip_suffix('#')?, radix(prefix)?));
+        }
+    }
+    if let Some(digits) = input.strip_suffix('h') {
+        if digits.starts_with(|c: char| c.is_ascii_digit()) {
+            return Some((strip_hex_pad(digits), 16));
+        }
+    }
+    let octal = |s: &str| s.chars().all(|c| ('0' ..= '7').contains(&c));
+    if input.len() > 1 && input.starts_with('0') && octal(input) {
+        return Some((&input[1 ..], 8));
+    }
+    None
+}
+
+/// The weight of the radices that are common in practice.
+fn common_weight(radix: usize) -> f64 {
+    match radix {
+        10 => 0.4,
+        16 => 0.25,
+        2 => 0.2,
+        8 => 0.1,
+        _ => 0.0,
+    }
+}
+
+fn candidates(input: &str) -> RadixResult<Vec<Candidate>> {
+    // Reading the string in the largest radix validates it, and scores the
+    // digits of other scripts, e.g. `٤٢`, as their ASCII forms.
+    let num: RadixNum = RadixNum::from_str(input, MAX_RADIX)?;
+    let digits: &str = num.as_str();
+    let max_digit: usize = digits.chars()
+        .map(char_to_digit)
+        .collect::<RadixResult<Vec<usize>>>()?
+        .into_iter()
+        .max()
+        .unwrap_or(0);
+    let smallest: usize = (max_digit + 1).max(2);
+    let has_letters: bool = digits.chars().any(|c| c.is_ascii_alphabetic());
+    let mut radices: Vec<usize> = vec![smallest, 2, 8, 10, 16, 36];
+    radices.retain(|&radix| radix >= smallest);
+    radices.sort_unstable();
+    radices.dedup();
+
+    let score = |radix: usize| -> f64 {
+        let mut score: f64 = 0.05 + common_weight(radix);
+        if radix == smallest { score += 0.25; }
+        if radix == 16 && has_letters && digits.len().is_multiple_of(2) {
+            score += 0.15;
+        }
+        if radix == 2 && digits.len().is_multiple_of(4) { score += 0.1; }
+        score
+    };
+    let total: f64 = radices.iter().map(|&radix| score(radix)).sum();
+    let mut candidates: Vec<Candidate> = radices.iter()
+        .map(|&radix| Ok(Candidate {
+            num: RadixNum::from_str(digits, radix)?,
+            confidence: score(radix) / total,
+        }))
+        .collect::<RadixResult<Vec<Candidate>>>()?;
+    candidates.sort_by(|a, b| b.confidence.total_cmp(&a.confidence)
+        .then(a.radix().cmp(&b.radix())));
+    Ok(candidates)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::detect::*;
+
+    fn ranking(input: &str) -> Vec<usize> {
+        match RadixNum::parse_auto(input) {
+            Ok(Detected::Candidates(candidates)) =>
+                candidates.iter().map(Candidate::radix).collect(),
+            other => panic!("expected candidates, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn literals() -> RadixResult<()> {
+        let literal = |digits, radix| {
+            RadixNum::from_str(digits, radix).map(Detected::Literal)
+        };
+        assert_eq!(literal("FF", 16)?,   RadixNum::parse_auto("0xFF")?);
+        assert_eq!(literal("17", 8)?,    RadixNum::parse_auto("0o17")?);
+        assert_eq!(literal("17", 8)?,    RadixNum::parse_auto("017")?);
+        assert_eq!(literal("101", 2)?,   RadixNum::parse_auto("0b101")?);
+        assert_eq!(literal("ZZ", 36)?,   RadixNum::parse_auto("ZZr36")?);
+        assert_eq!(literal("FF", 16)?,   RadixNum::parse_auto("16#FF#")?);
+        assert_eq!(literal("BE", 16)?,   RadixNum::parse_auto("0BEh")?);
+        assert_eq!(literal("3FARFNF", 32)?,
+                   RadixNum::parse_auto("3FARFNFr32")?);
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "0x1G".to_string(),
+            position: 3,
+            digits: vec!['G'],
+            radix: 16,
+        }), RadixNum::parse_auto("0x1G"));
+        assert_eq!(Err(RadixErr::EmptyInput), RadixNum::parse_auto("0x"));
+        assert!(RadixNum::parse_auto("13r3").is_err());
+        assert!(RadixNum::parse_auto("8#9#").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn display_roundtrip() -> RadixResult<()> {
+        for radix in 2 ..= 36 {
+            for &value in &[0_u32, 1, 35, 1234567] {
+                let num = RadixNum::from(value).with_radix(radix)?;
+                let parsed = RadixNum::parse_auto(&format!("{}", num))?;
+                assert_eq!(Detected::Literal(num), parsed);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn bare() -> RadixResult<()> {
+        assert_eq!(vec![2, 10, 16, 8, 36], ranking("1010"));
+        assert_eq!(vec![10, 4, 16, 8, 36], ranking("123"));
+        assert_eq!(vec![16, 36],           ranking("FF"));
+        assert_eq!(vec![16, 15, 36],       ranking("dead"));
+        assert_eq!(vec![16, 36],           ranking("0beef"));
+        assert_eq!(vec![36],               ranking("ZZ"));
+        if let Detected::Candidates(candidates) = RadixNum::parse_auto("1F")? {
+            let total: f64 = candidates.iter().map(Candidate::confidence).sum();
+            assert!((total - 1.0).abs() < 1e-9);
+            assert_eq!(31, candidates[0].num().as_decimal()?);
+        }
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "1-2".to_string(),
+            position: 1,
+            digits: vec!['-'],
+            radix: 36,
+        }), RadixNum::parse_auto("1-2"));
+        assert_eq!(Err(RadixErr::EmptyInput), RadixNum::parse_auto(" "));
+        assert_eq!(vec![10, 5, 16, 8, 36], ranking("٤٢"));
+        assert_eq!(Ok(42), "٤٢".parse::<RadixNum>()?.as_decimal());
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+//! Integer literals in the syntax of programming languages, e.g. `0o17`
+//! in Rust, `017` in C, `16#FF#` in Ada or `8'hFF` in Verilog.
+
+use crate::parse::ParseOptions;
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// A programming language's syntax for integer literals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Dialect {
+    /// The syntax of `Display`: `0b1010`, `017`, `0xFF`, and `ZZr36` for
+    /// any other radix.
+    Native,
+    /// `0b1010`, `017`, `0xFF`, with `u`/`l` suffixes and `'` separators.
+    C,
+    /// `0b1010`, `0o17`, `0xFF`, with type suffixes and `_` separators.
+    Rust,
+    /// `0b1010`, `0o17`, `0xFF`, with `_` separators.
+    Python,
+    /// `2#1010#`, `16#FF#`, for radices 2 to 16, with `_` separators.
+    Ada,
+    /// `2r1010`, `16rFF`, `36rZZ`.
+    Smalltalk,
+    /// `#b1010`, `#o17`, `#xFF`, and `#36rZZ` for any other radix.
+    CommonLisp,
+    /// `B"1010"`, `O"17"`, `X"FF"`, `D"255"`, with `_` separators.
+    Vhdl,
+    /// `4'b1010`, `4'o17`, `8'hFF`, `8'd255`, sized to the number of bits
+    /// of the value, with `_` separators.
+    Verilog,
+}
+
+impl Dialect {
+    /// Write `num` as a literal, in the radix it is stored in.
+    pub fn print(self, num: &RadixNum) -> RadixResult<String> {
+        let (radix, digits) = (num.radix(), num.as_str());
+        let unsupported = RadixErr::RadixNotSupported(radix);
+        Ok(match (self, radix) {
+            (Dialect::Native, _) => num.to_string(),
+            (Dialect::C, _) | (Dialect::Rust, _) | (Dialect::Python, _) => {
+                let prefix: &str = c_prefix(self, radix).ok_or(unsupported)?;
+                format!("{}{}", prefix, digits)
+            },
+            (Dialect::Ada, 10) | (Dialect::Smalltalk, 10) => digits.to_string(),
+            (Dialect::Ada, 2 ..= 16) => format!("{}#{}#", radix, digits),
+            (Dialect::Ada, _) => return Err(unsupported),
+            (Dialect::Smalltalk, _) => format!("{}r{}", radix, digits),
+            (Dialect::CommonLisp, 10) => digits.to_string(),
+            (Dialect::CommonLisp, _) => match base_letter(radix) {
+                Some(letter) => format!("#{}{}", letter, digits),
+                None => format!("#{}r{}", radix, digits),
+            },
+            (Dialect::Vhdl, _) => {
+                let letter: char = base_letter(radix).ok_or(unsupported)?;
+                format!("{}\"{}\"", letter.to_ascii_uppercase(), digits)
+            },
+            (Dialect::Verilog, _) => {
+                let letter: char = match base_letter(radix) {
+                    Some('x') => 'h',
+                    Some(letter) => letter,
+                    None => return Err(unsupported),
+                };
+                format!("{}'{}{}", bit_len(num)?.max(1), letter, digits)
+            },
+        })
+    }
+
+    /// Parse a literal into a `RadixNum` in the radix of the literal.
+    pub fn parse(self, literal: &str) -> RadixResult<RadixNum> {
+        let input: &str = literal.trim();
+        if input.is_empty() { return Err(RadixErr::EmptyInput); }
+        match self {
+            Dialect::Native => {
+                if let Some((digits, radix)) = input.rsplit_once('r') {
+                    let radix: usize = parse_radix(radix)?;
+                    return digits_in(literal, digits, radix, None);
+                }
+                self.parse_prefixed(literal, input, None)
+            },
+            Dialect::C => {
+                let input: &str = input.trim_end_matches(['u', 'U', 'l', 'L']);
+                self.parse_prefixed(literal, input, Some('\''))
+            },
+            Dialect::Rust => {
+                let input: &str = strip_rust_suffix(input);
+                self.parse_prefixed(literal, input, Some('_'))
+            },
+            Dialect::Python => self.parse_prefixed(literal, input, Some('_')),
+            Dialect::Ada => match input.split_once('#') {
+                Some((radix, rest)) => {
+                    let digits: &str = rest.strip_suffix('#')
+                        .ok_or(RadixErr::UnexpectedEndOfInput)?;
+                    match parse_radix(radix)? {
+                        radix @ 2 ..= 16 =>
+                            digits_in(literal, digits, radix, Some('_')),
+                        radix => Err(RadixErr::RadixNotSupported(radix)),
+                    }
+                },
+                None => digits_in(literal, input, 10, Some('_')),
+            },
+            Dialect::Smalltalk => match input.split_once('r') {
+                Some((radix, digits)) =>
+                    digits_in(literal, digits, parse_radix(radix)?, None),
+                None => digits_in(literal, input, 10, None),
+            },
+            Dialect::CommonLisp => match input.strip_prefix('#') {
+                Some(rest) => match rest.split_once(['r', 'R']) {
+                    Some((radix, digits)) if !radix.is_empty() =>
+                        digits_in(literal, digits, parse_radix(radix)?, None),
+                    _ => {
+                        let (letter, digits) = split_first(rest)?;
+                        digits_in(literal, digits, letter_base(letter)?, None)
+                    },
+                },
+                None => {
+                    let digits: &str = input.trim_end_matches('.');
+                    digits_in(literal, digits, 10, None)
+                },
+            },
+            Dialect::Vhdl => match input.find('"') {
+                Some(1) => {
+                    let (letter, rest) = split_first(input)?;
+                    let digits: &str = rest[1 ..].strip_suffix('"')
+                        .ok_or(RadixErr::UnexpectedEndOfInput)?;
+                    digits_in(literal, digits, letter_base(letter)?, Some('_'))
+                },
+                Some(_) => Err(RadixErr::IllegalChar('"')),
+                None => digits_in(literal, input, 10, Some('_')),
+            },
+            Dialect::Verilog => match input.split_once('\'') {
+                Some((size, rest)) => {
+                    // An `s` marks a signed literal.
+                    let rest: &str =
+                        rest.strip_prefix(['s', 'S']).unwrap_or(rest);
+                    let (letter, digits) = split_first(rest)?;
+                    let radix: usize = match letter.to_ascii_lowercase() {
+                        'h' => 16,
+                        letter => letter_base(letter)?,
+                    };
+                    let num: RadixNum =
+                        digits_in(literal, digits, radix, Some('_'))?;
+                    if size.is_empty() { return Ok(num); }
+                    let width: usize = parse_decimal(size)?;
+                    if bit_len(&num)? > width {
+                        return Err(RadixErr::WidthExceeded { width, radix: 2 });
+                    }
+                    Ok(num)
+                },
+                None => digits_in(literal, input, 10, Some('_')),
+            },
+        }
+    }
+
+    /// Parse a literal of a dialect with C-like `0b`, `0o` and `0x`
+    /// prefixes. In C and in `Native`, a leading `0` means octal. The
+    /// `input` is a slice of the whole `literal`.
+    fn parse_prefixed(
+        self,
+        literal: &str,
+        input: &str,
+        separator: Option<char>,
+    ) -> RadixResult<RadixNum> {
+        let mut chars = input.chars();
+        let radix: usize = match (chars.next(), chars.next()) {
+            (Some('0'), Some('b')) | (Some('0'), Some('B')) => 2,
+            (Some('0'), Some('x')) | (Some('0'), Some('X')) => 16,
+            (Some('0'), Some('o')) | (Some('0'), Some('O'))
+                if self != Dialect::C => 8,
+            (Some('0'), Some(_))
+                if self == Dialect::C || self == Dialect::Native => {
+                return digits_in(literal, &input[1 ..], 8, separator);
+            },
+            (Some('0'), Some(_)) => return Err(RadixErr::IllegalChar('0')),
+            _ => return digits_in(literal, input, 10, separator),
+        };
+        digits_in(literal, &input[2 ..], radix, separator)
+    }
+}
+
+/// The prefix of a C-like dialect for a `radix`.
+fn c_prefix(dialect: Dialect, radix: usize) -> Option<&'static str> {
+    match (dialect, radix) {
+        (_, 2) => Some("0b"),
+        (Dialect::C, 8) => Some("0"),
+        (_, 8) => Some("0o"),
+        (_, 10) => Some(""),
+        (_, 16) => Some("0x"),
+        _ => None,
+    }
+}
+
+/// The letter that marks a radix, i.e. `b`, `o`, `d` or `x`.
+fn base_letter(radix: usize) -> Option<char> {
+    match radix {
+        2 => Some('b'),
+        8 => Some('o'),
+        10 => Some('d'),
+        16 => Some('x'),
+        _ => None,
+    }
+}
+
+/// The radix marked by a letter, i.e. `b`, `o`, `d` or `x`.
+fn letter_base(letter: char) -> RadixResult<usize> {
+    match letter.to_ascii_lowercase() {
+        'b' => Ok(2),
+        'o' => Ok(8),
+        'd' => Ok(10),
+        'x' => Ok(16),
+        _ => Err(RadixErr::IllegalChar(letter)),
+    }
+}
+
+fn split_first(input: &str) -> RadixResult<(char, &str)> {
+    let letter: char = input.chars().next()
+        .ok_or(RadixErr::UnexpectedEndOfInput)?;
+    Ok((letter, &input[letter.len_utf8() ..]))
+}
+
+/// Parse a radix written in decimal, e.g. `16` in `16#FF#`.
+fn parse_radix(radix: &str) -> RadixResult<usize> {
+    let radix: usize = parse_decimal(radix)?;
+    RadixNum::validate_radix(radix)?;
+    Ok(radix)
+}
+
+fn parse_decimal(number: &str) -> RadixResult<usize> {
+    if let Some(c) = number.chars().find(|c| !c.is_ascii_digit()) {
+        return Err(RadixErr::IllegalChar(c));
+    }
+    number.parse().map_err(|_| RadixErr::EmptyInput)
+}
+
+/// The number of bits of the value of `num`.
+fn bit_len(num: &RadixNum) -> RadixResult<usize> {
+    Ok((u128::BITS - num.as_u128()?.leading_zeros()) as usize)
+}
+
+/// Parse `digits`, a slice of the literal `input`, in a certain `radix`,
+/// where `separator` may appear between digits.
+fn digits_in(
+    input: &str,
+    digits: &str,
+    radix: usize,
+    separator: Option<char>,
+) -> RadixResult<RadixNum> {
+    let options: ParseOptions = match separator {
+        Some(separator) => ParseOptions::new().with_separator(separator)?,
+        None => ParseOptions::new(),
+    };
+    RadixNum::from_str_in(input, digits, radix, &options)
+}
+
+fn strip_rust_suffix(input: &str) -> &str {
+    let types = ["8", "16", "32", "64", "128", "size"];
+    for &sign in &["u", "i"] {
+        for &bits in types.iter() {
+            let suffix: String = format!("{}{}", sign, bits);
+            if let Some(input) = input.strip_suffix(&suffix) {
+                return input.trim_end_matches('_');
+            }
+        }
+    }
+    input
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::dialect::*;
+
+    #[test]
+    fn print() -> RadixResult<()> {
+        let bin = RadixNum::from_str("1010", 2)?;
+        let oct = RadixNum::from_str("17", 8)?;
+        let hex = RadixNum::from_str("FF", 16)?;
+        let dec = RadixNum::from_str("255", 10)?;
+        let b36 = RadixNum::from_str("ZZ", 36)?;
+        assert_eq!("0o17",      Dialect::Rust.print(&oct)?);
+        assert_eq!("017",       Dialect::C.print(&oct)?);
+        assert_eq!("0b1010",    Dialect::Python.print(&bin)?);
+        assert_eq!("16#FF#",    Dialect::Ada.print(&hex)?);
+        assert_eq!("36rZZ",     Dialect::Smalltalk.print(&b36)?);
+        assert_eq!("#xFF",      Dialect::CommonLisp.print(&hex)?);
+        assert_eq!("#36rZZ",    Dialect::CommonLisp.print(&b36)?);
+        assert_eq!("X\"FF\"",   Dialect::Vhdl.print(&hex)?);
+        assert_eq!("8'hFF",     Dialect::Verilog.print(&hex)?);
+        assert_eq!("8'd255",    Dialect::Verilog.print(&dec)?);
+        assert_eq!("017",       Dialect::Native.print(&oct)?);
+        assert_eq!("ZZr36",     Dialect::Native.print(&b36)?);
+        assert_eq!(Err(RadixErr::RadixNotSupported(36)),
+                   Dialect::Rust.print(&b36));
+        assert_eq!(Err(RadixErr::RadixNotSupported(36)),
+                   Dialect::Ada.print(&b36));
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> RadixResult<()> {
+        assert_eq!(RadixNum::from_str("17", 8)?,
+                   Dialect::Rust.parse("0o17")?);
+        assert_eq!(RadixNum::from_str("10", 2)?,
+                   Dialect::Rust.parse("0b1_0")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   Dialect::Rust.parse("0xFFu8")?);
+        assert_eq!(RadixNum::from_str("17", 8)?,   Dialect::C.parse("017")?);
+        assert_eq!(RadixNum::from_str("1F", 16)?,  Dialect::C.parse("0x1Fu")?);
+        assert_eq!(RadixNum::from_str("1F", 16)?,
+                   Dialect::C.parse("0x1FULL")?);
+        assert_eq!(RadixNum::from_str("17", 8)?,
+                   Dialect::Python.parse("0O17")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   Dialect::Ada.parse("16#FF#")?);
+        assert_eq!(RadixNum::from_str("ZZ", 36)?,
+                   Dialect::Smalltalk.parse("36rZZ")?);
+        assert_eq!(RadixNum::from_str("1F", 16)?,
+                   Dialect::CommonLisp.parse("#x1F")?);
+        assert_eq!(RadixNum::from_str("ZZ", 36)?,
+                   Dialect::CommonLisp.parse("#36rZZ")?);
+        assert_eq!(RadixNum::from_str("1F", 16)?,
+                   Dialect::Vhdl.parse("X\"1F\"")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   Dialect::Verilog.parse("8'hFF")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   Dialect::Verilog.parse("'hF_F")?);
+        assert_eq!(RadixNum::from_str("FF", 16)?,
+                   Dialect::Verilog.parse("64'hFF")?);
+        assert_eq!(RadixNum::from_str("ZZ", 36)?,
+                   Dialect::Native.parse("ZZr36")?);
+        assert_eq!(RadixNum::from_str("42", 10)?,
+                   Dialect::Native.parse("42")?);
+        assert_eq!(Err(RadixErr::IllegalChar('0')),
+                   Dialect::Python.parse("017"));
+        assert_eq!(Err(RadixErr::RadixNotSupported(17)),
+                   Dialect::Ada.parse("17#GG#"));
+        assert_eq!(Err(RadixErr::UnexpectedEndOfInput),
+                   Dialect::Ada.parse("16#FF"));
+        assert_eq!(Err(RadixErr::WidthExceeded { width: 4, radix: 2 }),
+                   Dialect::Verilog.parse("4'hFF"));
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "0xF_G".to_string(),
+            position: 4,
+            digits: vec!['G'],
+            radix: 16,
+        }), Dialect::Rust.parse("0xF_G"));
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: " 8'hF_G_H".to_string(),
+            position: 6,
+            digits: vec!['G', 'H'],
+            radix: 16,
+        }), Dialect::Verilog.parse(" 8'hF_G_H"));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let dialects = [
+            Dialect::Native, Dialect::C, Dialect::Rust, Dialect::Python,
+            Dialect::Ada, Dialect::Smalltalk, Dialect::CommonLisp,
+            Dialect::Vhdl, Dialect::Verilog,
+        ];
+        for &dialect in dialects.iter() {
+            for &radix in &[2, 8, 10, 16] {
+                let num = RadixNum::from(48879_u32).with_radix(radix)?;
+                assert_eq!(num, dialect.parse(&dialect.print(&num)?)?);
+            }
+        }
+        Ok(())
+    }
+}
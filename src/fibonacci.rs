@@ -0,0 +1,204 @@
+//! Zeckendorf representations and Fibonacci coding of integers.
+
+use crate::{RadixErr, RadixResult};
+
+/// The Fibonacci numbers `F(2), F(3), ...` that fit in a `u128`, i.e.
+/// `1, 2, 3, 5, 8, ...`.
+fn fibonacci_numbers() -> Vec<u128> {
+    let mut fibs: Vec<u128> = vec![1, 2];
+    loop {
+        let (a, b) = (fibs[fibs.len() - 2], fibs[fibs.len() - 1]);
+        match a.checked_add(b) {
+            Some(next) => fibs.push(next),
+            None => return fibs,
+        }
+    }
+}
+
+/// Write `number` as a sum of non-consecutive Fibonacci numbers.
+/// The terms are returned in descending order.
+pub fn zeckendorf(number: u128) -> Vec<u128> {
+    let mut number: u128 = number;
+    let mut terms: Vec<u128> = vec![];
+    for &fib in fibonacci_numbers().iter().rev() {
+        if fib <= number {
+            terms.push(fib);
+            number -= fib;
+        }
+    }
+    terms
+}
+
+/// Render the Zeckendorf representation of `number` as a digit string,
+/// most significant digit first. The rightmost digit stands for `F(2) = 1`.
+pub fn to_zeckendorf(number: u128) -> String {
+    if number == 0 { return String::from("0"); }
+    let mut number: u128 = number;
+    let mut digits: String = String::new();
+    for &fib in fibonacci_numbers().iter().rev() {
+        if fib <= number {
+            digits.push('1');
+            number -= fib;
+        } else if !digits.is_empty() {
+            digits.push('0');
+        }
+    }
+    digits
+}
+
+/// Parse a Zeckendorf digit string as produced by `to_zeckendorf`.
+/// Adjacent `1` digits are rejected, as they are not in Zeckendorf form.
+pub fn from_zeckendorf(digits: &str) -> RadixResult<u128> {
+    let bits: Vec<bool> = to_bits(digits)?;
+    if bits.is_empty() { return Err(RadixErr::EmptyInput); }
+    if bits.windows(2).any(|pair| pair[0] && pair[1]) {
+        return Err(RadixErr::IllegalChar('1'));
+    }
+    sum_terms(bits.iter().rev().cloned())
+}
+
+/// Fibonacci-code a positive `number`: its Zeckendorf digits, least
+/// significant first, followed by an extra `1` so the code ends in `11`.
+pub fn encode(number: u128) -> RadixResult<String> {
+    if number == 0 { return Err(RadixErr::ValueOutOfRange(number)); }
+    let mut code: String = to_zeckendorf(number).chars().rev().collect();
+    code.push('1');
+    Ok(code)
+}
+
+/// Fibonacci-code each of `numbers` and concatenate the codewords.
+pub fn encode_all(numbers: &[u128]) -> RadixResult<String> {
+    numbers.iter().map(|&number| encode(number)).collect()
+}
+
+/// Decode a single Fibonacci codeword, which must end in `11`.
+pub fn decode(code: &str) -> RadixResult<u128> {
+    let mut decoder = Decoder::new(to_bits(code)?);
+    let number: u128 = decoder.next().ok_or(RadixErr::EmptyInput)??;
+    match decoder.next() {
+        None => Ok(number),
+        Some(_) => Err(RadixErr::IllegalChar('1')),
+    }
+}
+
+/// Decode a stream of concatenated Fibonacci codewords.
+pub fn decode_all(code: &str) -> RadixResult<Vec<u128>> {
+    Decoder::new(to_bits(code)?).collect()
+}
+
+/// An iterator that decodes Fibonacci codewords from a stream of bits.
+#[derive(Clone, Debug)]
+pub struct Decoder<I: Iterator<Item=bool>> {
+    bits: I,
+    fibs: Vec<u128>,
+}
+
+impl<I: Iterator<Item=bool>> Decoder<I> {
+    pub fn new<B>(bits: B) -> Self
+    where B: IntoIterator<IntoIter=I, Item=bool> {
+        Decoder { bits: bits.into_iter(), fibs: fibonacci_numbers() }
+    }
+}
+
+impl<I: Iterator<Item=bool>> Iterator for Decoder<I> {
+    type Item = RadixResult<u128>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut number: u128 = 0;
+        let mut previous: bool = false;
+        for idx in 0 .. {
+            let bit: bool = match self.bits.next() {
+                Some(bit) => bit,
+                None if idx == 0 => return None,
+                None => return Some(Err(RadixErr::UnexpectedEndOfInput)),
+            };
+            if bit && previous { return Some(Ok(number)); }
+            if bit {
+                let term = self.fibs.get(idx)
+                    .ok_or(RadixErr::ArithmeticOverflow);
+                let sum = term.and_then(|&fib| {
+                    number.checked_add(fib).ok_or(RadixErr::ArithmeticOverflow)
+                });
+                match sum {
+                    Ok(sum) => number = sum,
+                    Err(err) => return Some(Err(err)),
+                }
+            }
+            previous = bit;
+        }
+        unreachable!()
+    }
+}
+
+/// Sum the Fibonacci numbers selected by `bits`, least significant first.
+fn sum_terms(bits: impl Iterator<Item=bool>) -> RadixResult<u128> {
+    let fibs: Vec<u128> = fibonacci_numbers();
+    let mut number: u128 = 0;
+    for (idx, bit) in bits.enumerate() {
+        if !bit { continue; }
+        let fib: u128 = *fibs.get(idx).ok_or(RadixErr::ArithmeticOverflow)?;
+        number = number.checked_add(fib).ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(number)
+}
+
+fn to_bits(digits: &str) -> RadixResult<Vec<bool>> {
+    digits.trim().chars().map(|c| match c {
+        '0' => Ok(false),
+        '1' => Ok(true),
+        c => Err(RadixErr::IllegalChar(c)),
+    }).collect()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::fibonacci::*;
+
+    #[test]
+    fn zeckendorf_terms() {
+        assert_eq!(Vec::<u128>::new(), zeckendorf(0));
+        assert_eq!(vec![89, 8, 3],     zeckendorf(100));
+        assert_eq!("0",                to_zeckendorf(0));
+        assert_eq!("1000010100",       to_zeckendorf(100));
+        assert_eq!(Ok(100),            from_zeckendorf("1000010100"));
+        assert_eq!(Err(RadixErr::IllegalChar('1')), from_zeckendorf("110"));
+        assert_eq!(Err(RadixErr::IllegalChar('2')), from_zeckendorf("120"));
+    }
+
+    #[test]
+    fn encode_numbers() {
+        assert_eq!(Ok(String::from("11")),     encode(1));
+        assert_eq!(Ok(String::from("011")),    encode(2));
+        assert_eq!(Ok(String::from("0011")),   encode(3));
+        assert_eq!(Ok(String::from("1011")),   encode(4));
+        assert_eq!(Ok(String::from("01011")),  encode(7));
+        assert_eq!(Ok(String::from("101011")), encode(12));
+        assert_eq!(Err(RadixErr::ValueOutOfRange(0)), encode(0));
+    }
+
+    #[test]
+    fn decode_codewords() {
+        assert_eq!(Ok(12), decode("101011"));
+        assert_eq!(Ok(vec![1, 2, 3, 4, 7, 12]),
+                   decode_all("110110011101101011101011"));
+        assert_eq!(Err(RadixErr::UnexpectedEndOfInput), decode("1010"));
+        assert_eq!(Err(RadixErr::IllegalChar('1')),     decode("1111"));
+        let bits = vec![false, true, true, true, true];
+        let decoded: Vec<_> = Decoder::new(bits).collect();
+        assert_eq!(vec![Ok(2), Ok(1)], decoded);
+    }
+
+    #[test]
+    fn roundtrip_u128() -> RadixResult<()> {
+        let max: u128 = u128::MAX;
+        for &number in &[1, 2, 1000, u64::MAX as u128, max - 1, max] {
+            assert_eq!(number, from_zeckendorf(&to_zeckendorf(number))?);
+            assert_eq!(number, decode(&encode(number)?)?);
+        }
+        let numbers = vec![u128::MAX, 1, 42, u128::MAX / 3];
+        assert_eq!(numbers, decode_all(&encode_all(&numbers)?)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,200 @@
+//! Fixed-width views of signed integers, e.g. to read `0xFFFE` as an
+//! `i16`, in two's complement, one's complement, sign-magnitude or
+//! offset-binary encoding.
+
+use crate::{scale_width, RadixErr, RadixNum, RadixResult};
+
+/// The ways in which a signed integer can be encoded in a bit pattern.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SignEncoding {
+    /// Negative values are `2^bits - |value|`.
+    TwosComplement,
+    /// Negative values have all bits of `|value|` inverted.
+    OnesComplement,
+    /// The top bit is the sign, the other bits are `|value|`.
+    SignMagnitude,
+    /// Values are offset by `2^(bits - 1)`, a.k.a. excess-K.
+    OffsetBinary,
+}
+
+/// A signed integer encoding of a fixed number of bits, in `1..=128`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FixedWidth {
+    bits: u32,
+    encoding: SignEncoding,
+}
+
+impl FixedWidth {
+    pub fn new(bits: u32, encoding: SignEncoding) -> RadixResult<Self> {
+        if bits == 0 || bits > 128 {
+            return Err(RadixErr::WidthNotSupported(bits as usize));
+        }
+        Ok(FixedWidth { bits, encoding })
+    }
+
+    pub fn bits(&self) -> u32 { self.bits }
+
+    pub fn encoding(&self) -> SignEncoding { self.encoding }
+
+    /// The smallest value that can be encoded.
+    pub fn min(&self) -> i128 {
+        match self.encoding {
+            SignEncoding::TwosComplement |
+            SignEncoding::OffsetBinary => (self.half() as i128).wrapping_neg(),
+            SignEncoding::OnesComplement |
+            SignEncoding::SignMagnitude => -self.max(),
+        }
+    }
+
+    /// The largest value that can be encoded.
+    pub fn max(&self) -> i128 {
+        (self.half() - 1) as i128
+    }
+
+    /// Encode `value` as a bit pattern.
+    pub fn encode(&self, value: i128) -> RadixResult<u128> {
+        if value < self.min() || value > self.max() {
+            return Err(self.exceeded());
+        }
+        let magnitude: u128 = value.unsigned_abs();
+        let negative: bool = value < 0;
+        Ok(match self.encoding {
+            SignEncoding::TwosComplement => value as u128 & self.mask(),
+            SignEncoding::OnesComplement if negative =>
+                !magnitude & self.mask(),
+            SignEncoding::SignMagnitude if negative => self.half() | magnitude,
+            SignEncoding::OnesComplement |
+            SignEncoding::SignMagnitude => magnitude,
+            SignEncoding::OffsetBinary =>
+                (value as u128).wrapping_add(self.half()) & self.mask(),
+        })
+    }
+
+    /// Decode a bit `pattern`, which must fit in the width.
+    /// Negative zero decodes to `0`.
+    pub fn decode(&self, pattern: u128) -> RadixResult<i128> {
+        if pattern & !self.mask() != 0 { return Err(self.exceeded()); }
+        let sign_bit: bool = pattern & self.half() != 0;
+        Ok(match self.encoding {
+            SignEncoding::TwosComplement if sign_bit =>
+                (pattern | !self.mask()) as i128,
+            SignEncoding::OnesComplement if sign_bit =>
+                -((!pattern & self.mask()) as i128),
+            SignEncoding::SignMagnitude if sign_bit =>
+                -((pattern & !self.half()) as i128),
+            // In offset binary, the sign bit is set for non-negative values.
+            SignEncoding::OffsetBinary if sign_bit =>
+                (pattern - self.half()) as i128,
+            SignEncoding::OffsetBinary =>
+                0i128.wrapping_sub((self.half() - pattern) as i128),
+            _ => pattern as i128,
+        })
+    }
+
+    /// Encode `value`, and represent the bit pattern in a certain `radix`,
+    /// with as many digits as any pattern of the width needs.
+    pub fn to_radix(&self, value: i128, radix: usize) -> RadixResult<RadixNum> {
+        let width: usize = scale_width(self.bits as usize, 2, radix);
+        RadixNum::from_decimal(self.encode(value)?, radix)?.with_width(width)
+    }
+
+    /// Decode the bit pattern represented by `num`.
+    pub fn from_radix(&self, num: &RadixNum) -> RadixResult<i128> {
+        self.decode(num.as_u128()?)
+    }
+
+    /// Decode the bit pattern represented by the digits `base`, encoded in a
+    /// certain `radix`, e.g. `"FFFE"` in radix 16 is `-2` as a 16 bit two's
+    /// complement integer.
+    pub fn reinterpret(&self, base: &str, radix: usize) -> RadixResult<i128> {
+        self.decode(RadixNum::radix_x_to_u128(base, radix)?)
+    }
+
+    /// The value of the sign bit, i.e. `2^(bits - 1)`.
+    #[inline(always)]
+    fn half(&self) -> u128 { 1 << (self.bits - 1) }
+
+    #[inline(always)]
+    fn mask(&self) -> u128 { self.half() | (self.half() - 1) }
+
+    #[inline(always)]
+    fn exceeded(&self) -> RadixErr {
+        RadixErr::WidthExceeded { width: self.bits as usize, radix: 2 }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::fixed_width::*;
+
+    fn width(bits: u32, encoding: SignEncoding) -> FixedWidth {
+        FixedWidth::new(bits, encoding).expect("width")
+    }
+
+    #[test]
+    fn ranges() {
+        let twos = width(8, SignEncoding::TwosComplement);
+        assert_eq!((-128, 127), (twos.min(), twos.max()));
+        let ones = width(8, SignEncoding::OnesComplement);
+        assert_eq!((-127, 127), (ones.min(), ones.max()));
+        let wide = width(128, SignEncoding::TwosComplement);
+        assert_eq!((i128::MIN, i128::MAX), (wide.min(), wide.max()));
+        assert_eq!(Err(RadixErr::WidthNotSupported(0)),
+                   FixedWidth::new(0, SignEncoding::TwosComplement));
+        assert_eq!(Err(RadixErr::WidthNotSupported(129)),
+                   FixedWidth::new(129, SignEncoding::TwosComplement));
+    }
+
+    #[test]
+    fn reinterpret_register() -> RadixResult<()> {
+        let view = |encoding| width(16, encoding).reinterpret("FFFE", 16);
+        assert_eq!(-2,     view(SignEncoding::TwosComplement)?);
+        assert_eq!(-1,     view(SignEncoding::OnesComplement)?);
+        assert_eq!(-32766, view(SignEncoding::SignMagnitude)?);
+        assert_eq!(32766,  view(SignEncoding::OffsetBinary)?);
+        let overflow = RadixErr::WidthExceeded { width: 16, radix: 2 };
+        let twos = width(16, SignEncoding::TwosComplement);
+        assert_eq!(Err(overflow), twos.reinterpret("1FFFE", 16));
+        Ok(())
+    }
+
+    #[test]
+    fn encode_values() -> RadixResult<()> {
+        let twos = width(8, SignEncoding::TwosComplement);
+        assert_eq!("11111110", twos.to_radix(-2, 2)?.as_str());
+        assert_eq!("80",       twos.to_radix(-128, 16)?.as_str());
+        assert_eq!("00000010", twos.to_radix(2, 2)?.as_str());
+        assert_eq!("002",      twos.to_radix(2, 10)?.as_str());
+        let ones = width(8, SignEncoding::OnesComplement);
+        assert_eq!("FD",       ones.to_radix(-2, 16)?.as_str());
+        let sign_magnitude = width(8, SignEncoding::SignMagnitude);
+        assert_eq!("82",       sign_magnitude.to_radix(-2, 16)?.as_str());
+        let offset = width(8, SignEncoding::OffsetBinary);
+        assert_eq!("7E",       offset.to_radix(-2, 16)?.as_str());
+        let overflow = RadixErr::WidthExceeded { width: 8, radix: 2 };
+        assert_eq!(Err(overflow.clone()), twos.encode(128));
+        assert_eq!(Err(overflow), ones.encode(-128));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let encodings = [
+            SignEncoding::TwosComplement, SignEncoding::OnesComplement,
+            SignEncoding::SignMagnitude, SignEncoding::OffsetBinary,
+        ];
+        for &encoding in encodings.iter() {
+            for &bits in &[1, 8, 13, 64, 127, 128] {
+                let view = width(bits, encoding);
+                for &value in &[view.min(), -1, 0, 1, view.max()] {
+                    if value < view.min() || value > view.max() { continue; }
+                    let num = view.to_radix(value, 16)?;
+                    assert_eq!(value, view.from_radix(&num)?);
+                }
+            }
+        }
+        Ok(())
+    }
+}
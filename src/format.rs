@@ -0,0 +1,290 @@
+//! Configurable output formatting of a `RadixNum`, e.g. `+0x00ff_ffff`,
+//! through `RadixNum::display_with`.
+
+use crate::grouping::Grouping;
+use crate::RadixNum;
+use std::fmt;
+
+/// The way the radix of a number is marked.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AffixStyle {
+    /// As `Display` does: `0b` for radix 2, `0` for radix 8, `0x` for radix
+    /// 16, and an `rN` suffix otherwise.
+    Native,
+    /// `0b`, `0o` and `0x` prefixes for radix 2, 8 and 16, and an `rN`
+    /// suffix otherwise.
+    Prefix,
+    /// An `rN` suffix for every radix, e.g. `FFr16`.
+    Suffix,
+    /// No marker at all.
+    Bare,
+}
+
+impl AffixStyle {
+    /// The prefix, and whether there is an `rN` suffix, for a `radix`.
+    fn affixes(self, radix: usize) -> (&'static str, bool) {
+        match (self, radix) {
+            (AffixStyle::Bare, _) => ("", false),
+            (AffixStyle::Suffix, _) => ("", true),
+            (_, 2) => ("0b", false),
+            (AffixStyle::Native, 8) => ("0", false),
+            (AffixStyle::Prefix, 8) => ("0o", false),
+            (_, 16) => ("0x", false),
+            _ => ("", true),
+        }
+    }
+}
+
+/// The case of the digits above 9.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LetterCase {
+    Upper,
+    Lower,
+}
+
+/// Options for `RadixNum::display_with`. The defaults match `Display`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    affix: AffixStyle,
+    case: LetterCase,
+    min_digits: usize,
+    plus_sign: bool,
+    grouping: Option<Grouping>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        FormatOptions {
+            affix: AffixStyle::Native,
+            case: LetterCase::Upper,
+            min_digits: 0,
+            plus_sign: false,
+            grouping: None,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self { FormatOptions::default() }
+
+    /// Mark the radix in a certain `affix` style.
+    pub fn with_affix(self, affix: AffixStyle) -> Self {
+        FormatOptions { affix, ..self }
+    }
+
+    /// Write the digits above 9 in a certain letter `case`.
+    pub fn with_case(self, case: LetterCase) -> Self {
+        FormatOptions { case, ..self }
+    }
+
+    /// Pad the digits with leading zeros to at least `min_digits` digits.
+    pub fn with_min_digits(self, min_digits: usize) -> Self {
+        FormatOptions { min_digits, ..self }
+    }
+
+    /// Write a leading `+`, as numbers are never negative.
+    pub fn with_plus_sign(self, plus_sign: bool) -> Self {
+        FormatOptions { plus_sign, ..self }
+    }
+
+    /// Group the digits, including any zero padding, by `grouping`.
+    pub fn with_grouping(self, grouping: Grouping) -> Self {
+        FormatOptions { grouping: Some(grouping), ..self }
+    }
+
+    pub fn affix(&self) -> AffixStyle { self.affix }
+
+    pub fn case(&self) -> LetterCase { self.case }
+
+    pub fn min_digits(&self) -> usize { self.min_digits }
+
+    pub fn plus_sign(&self) -> bool { self.plus_sign }
+
+    pub fn grouping(&self) -> Option<&Grouping> { self.grouping.as_ref() }
+}
+
+/// A `Display` adapter created by `RadixNum::display_with`, which writes
+/// to the formatter without allocating.
+#[derive(Copy, Clone, Debug)]
+pub struct DisplayWith<'a> {
+    num: &'a RadixNum,
+    options: &'a FormatOptions,
+}
+
+impl RadixNum {
+    /// Format the number according to `options`, e.g.
+    /// `format!("{}", num.display_with(&options))`.
+    pub fn display_with<'a>(
+        &'a self,
+        options: &'a FormatOptions,
+    ) -> DisplayWith<'a> {
+        DisplayWith { num: self, options }
+    }
+}
+
+impl<'a> DisplayWith<'a> {
+    /// The number of characters written with `options`, before padding to
+    /// the formatter's width.
+    fn len(&self, options: &FormatOptions) -> usize {
+        let digits: usize = self.num.as_str().len().max(options.min_digits);
+        let separators: usize = options.grouping
+            .map_or(0, |grouping| grouping.separators(digits));
+        self.affix_len(options) + digits + separators
+    }
+
+    /// The number of characters of the sign and the radix marker.
+    fn affix_len(&self, options: &FormatOptions) -> usize {
+        let radix: usize = self.num.radix();
+        let (prefix, suffix) = options.affix.affixes(radix);
+        let suffix: usize = match (suffix, radix) {
+            (false, _) => 0,
+            (true, 0 ..= 9) => 2,
+            (true, _) => 3,
+        };
+        options.plus_sign as usize + prefix.len() + suffix
+    }
+
+    fn write(&self, f: &mut fmt::Formatter, options: &FormatOptions)
+        -> fmt::Result {
+        let digits: &str = self.num.as_str();
+        let (prefix, suffix) = options.affix.affixes(self.num.radix());
+        if options.plus_sign { f.write_str("+")?; }
+        f.write_str(prefix)?;
+        let padding: usize = options.min_digits.saturating_sub(digits.len());
+        let len: usize = padding + digits.len();
+        let padded = std::iter::repeat_n('0', padding).chain(digits.chars());
+        for (idx, digit) in padded.enumerate() {
+            if let Some(grouping) = &options.grouping {
+                if grouping.separator_before(idx, len) {
+                    fmt::Write::write_char(f, grouping.separator())?;
+                }
+            }
+            let digit: char = match options.case {
+                LetterCase::Upper => digit,
+                LetterCase::Lower => digit.to_ascii_lowercase(),
+            };
+            fmt::Write::write_char(f, digit)?;
+        }
+        if suffix { write!(f, "r{}", self.num.radix())?; }
+        Ok(())
+    }
+}
+
+impl<'a> fmt::Display for DisplayWith<'a> {
+    /// Honors the width, fill, alignment, `+` and `0` flags of the
+    /// formatter. Numbers are right-aligned by default, and `0` pads the
+    /// digits with zeros after the sign and prefix.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut options: FormatOptions = *self.options;
+        options.plus_sign |= f.sign_plus();
+        let width: usize = f.width().unwrap_or(0);
+        if f.sign_aware_zero_pad() {
+            // The fewest digits that fill the width along with the affixes
+            // and the separators between them.
+            let len: usize = width.saturating_sub(self.affix_len(&options));
+            let digits: usize = options.grouping
+                .map_or(len, |grouping| grouping.digits_filling(len));
+            options.min_digits = options.min_digits.max(digits);
+        }
+        let padding: usize = width.saturating_sub(self.len(&options));
+        let (before, after) = match f.align() {
+            _ if padding == 0 => (0, 0),
+            Some(fmt::Alignment::Left) => (0, padding),
+            Some(fmt::Alignment::Center) =>
+                (padding / 2, padding - padding / 2),
+            Some(fmt::Alignment::Right) | None => (padding, 0),
+        };
+        let fill: char = f.fill();
+        for _ in 0 .. before { fmt::Write::write_char(f, fill)?; }
+        self.write(f, &options)?;
+        for _ in 0 .. after { fmt::Write::write_char(f, fill)?; }
+        Ok(())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::format::*;
+    use crate::RadixResult;
+
+    #[test]
+    fn defaults() -> RadixResult<()> {
+        let options = FormatOptions::new();
+        for &radix in &[2, 8, 16, 32] {
+            let num = RadixNum::from(123456789_u32).with_radix(radix)?;
+            assert_eq!(num.to_string(), num.display_with(&options).to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn options() -> RadixResult<()> {
+        let num = RadixNum::from_str("FFFFFF", 16)?;
+        let options = FormatOptions::new()
+            .with_case(LetterCase::Lower)
+            .with_min_digits(8)
+            .with_plus_sign(true)
+            .with_grouping(Grouping::nibbles());
+        assert_eq!("+0x00ff_ffff", num.display_with(&options).to_string());
+        let suffix = FormatOptions::new().with_affix(AffixStyle::Suffix);
+        assert_eq!("FFFFFFr16", num.display_with(&suffix).to_string());
+        let bare = FormatOptions::new().with_affix(AffixStyle::Bare);
+        assert_eq!("FFFFFF", num.display_with(&bare).to_string());
+        let octal = RadixNum::from(8_u8).with_radix(8)?;
+        let prefix = FormatOptions::new().with_affix(AffixStyle::Prefix);
+        assert_eq!("0o10", octal.display_with(&prefix).to_string());
+        let base32 = RadixNum::from(1_000_000_u32).with_radix(32)?;
+        assert_eq!("ugi0r32", base32.display_with(&prefix
+            .with_case(LetterCase::Lower)).to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn formatter_flags() -> RadixResult<()> {
+        let num = RadixNum::from_str("FF", 16)?;
+        assert_eq!("      0xFF", format!("{:>10}", num));
+        assert_eq!("0xFF      ", format!("{:<10}", num));
+        assert_eq!("***0xFF***", format!("{:*^10}", num));
+        assert_eq!("      0xFF", format!("{:10}", num));
+        assert_eq!("0x000000FF", format!("{:010}", num));
+        assert_eq!("+0x00FF",    format!("{:+07}", num));
+        assert_eq!("FF",         format!("{:#}", num));
+        assert_eq!("000FF",      format!("{:#05}", num));
+        assert_eq!("0xFF",       format!("{:2}", num));
+        let base32 = RadixNum::from_str("V", 32)?;
+        assert_eq!("00Vr32",     format!("{:06}", base32));
+        let grouped = FormatOptions::new().with_grouping(Grouping::nibbles());
+        let binary = num.with_radix(2)?;
+        assert_eq!("  0b1111_1111",
+                   format!("{:>13}", binary.display_with(&grouped)));
+        assert_eq!("0b0_1111_1111",
+                   format!("{:012}", binary.display_with(&grouped)));
+        let wide: String = format!("{:060000}", binary.display_with(&grouped));
+        assert_eq!(60_000, wide.len());
+        assert!(wide.starts_with("0b000_0000_"));
+        Ok(())
+    }
+
+    #[test]
+    fn std_radix_traits() -> RadixResult<()> {
+        let num = RadixNum::from_str("255", 10)?;
+        assert_eq!("ff",       format!("{:x}", num));
+        assert_eq!("0xFF",     format!("{:#X}", num));
+        assert_eq!("377",      format!("{:o}", num));
+        assert_eq!("11111111", format!("{:b}", num));
+        assert_eq!("0b0000000011111111", format!("{:#018b}", num));
+        let base36 = RadixNum::from_str("73", 36)?;
+        assert_eq!("ff",       format!("{:x}", base36));
+        let big = RadixNum::from_str(&"F".repeat(40), 16)?;
+        assert_eq!("f".repeat(40), format!("{:x}", big));
+        assert_eq!("1".repeat(160), format!("{:b}", big));
+        let big = RadixNum::from_str(&format!("1{}", "0".repeat(39)), 10)?;
+        assert_eq!("0x2F050FE938943ACC45F65568000000000",
+                   format!("{:#X}", big));
+        assert_eq!("0", format!("{:o}", RadixNum::from_str("000", 7)?));
+        assert_eq!("  ff", format!("{:4x}", RadixNum::from_str("00FF", 16)?));
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! Reflected Gray codes, in binary and in any radix that `RadixNum`
+//! supports. Consecutive values differ in exactly one digit, by one.
+
+use crate::{char_to_digit, digit_to_char};
+use crate::{RadixErr, RadixNum, RadixResult};
+use std::convert::TryFrom;
+
+/// Compute the reflected binary Gray code of `number`.
+pub fn to_gray(number: u128) -> u128 {
+    number ^ (number >> 1)
+}
+
+/// Compute the number whose reflected binary Gray code is `gray`.
+pub fn from_gray(gray: u128) -> u128 {
+    let mut number: u128 = gray;
+    let mut shift: u32 = 1;
+    while shift < u128::BITS {
+        number ^= number >> shift;
+        shift *= 2;
+    }
+    number
+}
+
+/// Compute the reflected n-ary Gray code of `digits`, given most
+/// significant first. A digit is reflected, i.e. `d` becomes
+/// `radix - 1 - d`, when the number formed by the digits above it is odd.
+pub fn to_gray_digits(
+    digits: &[usize],
+    radix: usize,
+) -> RadixResult<Vec<usize>> {
+    RadixNum::validate_radix(radix)?;
+    let mut parity: usize = 0;
+    digits.iter().map(|&digit| {
+        if digit >= radix { return Err(RadixErr::IllegalDigit(digit)); }
+        let gray: usize = reflect(digit, radix, parity);
+        parity = (parity * radix + digit) % 2;
+        Ok(gray)
+    }).collect()
+}
+
+/// Compute the digits whose reflected n-ary Gray code is `gray`.
+pub fn from_gray_digits(
+    gray: &[usize],
+    radix: usize,
+) -> RadixResult<Vec<usize>> {
+    RadixNum::validate_radix(radix)?;
+    let mut parity: usize = 0;
+    gray.iter().map(|&gray| {
+        if gray >= radix { return Err(RadixErr::IllegalDigit(gray)); }
+        let digit: usize = reflect(gray, radix, parity);
+        parity = (parity * radix + digit) % 2;
+        Ok(digit)
+    }).collect()
+}
+
+/// Compute the reflected Gray code of `num`, in the radix of `num`.
+pub fn to_nary_gray(num: &RadixNum) -> RadixResult<RadixNum> {
+    let digits: Vec<usize> = digits_of(num)?;
+    let gray: Vec<usize> = to_gray_digits(&digits, num.radix())?;
+    with_width_of(RadixNum::from_digits(render(&gray)?, num.radix())?, num)
+}
+
+/// Compute the number whose reflected Gray code, in the radix of `gray`,
+/// is `gray`.
+pub fn from_nary_gray(gray: &RadixNum) -> RadixResult<RadixNum> {
+    let gray_digits: Vec<usize> = digits_of(gray)?;
+    let digits: Vec<usize> = from_gray_digits(&gray_digits, gray.radix())?;
+    with_width_of(RadixNum::from_digits(render(&digits)?, gray.radix())?, gray)
+}
+
+/// Give `result` the width of `num`, if it has one.
+fn with_width_of(result: RadixNum, num: &RadixNum) -> RadixResult<RadixNum> {
+    match num.width() {
+        Some(width) => result.with_width(width),
+        None => Ok(result),
+    }
+}
+
+/// Walk the reflected Gray sequence of all `width` digit strings in a
+/// certain `radix`, starting at all zeros.
+pub fn sequence(radix: usize, width: usize) -> RadixResult<GraySequence> {
+    RadixNum::validate_radix(radix)?;
+    let len: u128 = u32::try_from(width).ok()
+        .and_then(|width| (radix as u128).checked_pow(width))
+        .ok_or(RadixErr::ArithmeticOverflow)?;
+    Ok(GraySequence { radix, width, index: 0, len })
+}
+
+/// An iterator over the reflected Gray sequence of fixed width digit
+/// strings, created by `sequence`.
+#[derive(Clone, Debug)]
+pub struct GraySequence {
+    radix: usize,
+    width: usize,
+    index: u128,
+    len: u128,
+}
+
+impl Iterator for GraySequence {
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.len { return None; }
+        let mut number: u128 = self.index;
+        let mut digits: Vec<usize> = vec![0; self.width];
+        for digit in digits.iter_mut().rev() {
+            *digit = (number % self.radix as u128) as usize;
+            number /= self.radix as u128;
+        }
+        self.index += 1;
+        let gray: Vec<usize> = to_gray_digits(&digits, self.radix).ok()?;
+        render(&gray).ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.len - self.index).min(usize::MAX as u128);
+        (remaining as usize, Some(remaining as usize))
+    }
+}
+
+#[inline(always)]
+fn reflect(digit: usize, radix: usize, parity: usize) -> usize {
+    if parity == 1 { radix - 1 - digit } else { digit }
+}
+
+fn digits_of(num: &RadixNum) -> RadixResult<Vec<usize>> {
+    num.digits().map(char_to_digit).collect()
+}
+
+fn render(digits: &[usize]) -> RadixResult<String> {
+    digits.iter().map(|&digit| digit_to_char(digit)).collect()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::gray::*;
+
+    #[test]
+    fn binary() {
+        let codes: Vec<u128> = (0 .. 8).map(to_gray).collect();
+        assert_eq!(
+            vec![0b000, 0b001, 0b011, 0b010, 0b110, 0b111, 0b101, 0b100],
+            codes
+        );
+        for &n in &[0, 1, 2, 1000, u64::MAX as u128, u128::MAX] {
+            assert_eq!(n, from_gray(to_gray(n)));
+        }
+    }
+
+    #[test]
+    fn nary() -> RadixResult<()> {
+        assert_eq!(vec![1, 2], to_gray_digits(&[1, 0], 3)?);
+        assert_eq!(vec![1, 0], from_gray_digits(&[1, 2], 3)?);
+        assert_eq!(Err(RadixErr::IllegalDigit(3)), to_gray_digits(&[3], 3));
+        let num = RadixNum::from_str("1F", 16)?;
+        let gray = to_nary_gray(&num)?;
+        assert_eq!(16, gray.radix());
+        assert_eq!("1F", from_nary_gray(&gray)?.as_str());
+        let binary = RadixNum::from(6_u8).with_radix(2)?;
+        assert_eq!("101", to_nary_gray(&binary)?.as_str());
+        let padded = to_nary_gray(&RadixNum::from_str("0F", 16)?)?;
+        assert_eq!(Some(2), padded.width());
+        assert_eq!("0F", from_nary_gray(&padded)?.as_str());
+        let fixed = RadixNum::from(1_u8).with_radix(2)?.with_width(4)?;
+        assert_eq!("0001", to_nary_gray(&fixed)?.as_str());
+        Ok(())
+    }
+
+    #[test]
+    fn sequences() -> RadixResult<()> {
+        let codes: Vec<String> = sequence(3, 2)?.collect();
+        assert_eq!(vec!["00", "01", "02", "12", "11", "10", "20", "21", "22"],
+                   codes);
+        for &(radix, width) in &[(2, 5), (5, 3), (10, 3), (36, 2)] {
+            let codes: Vec<String> = sequence(radix, width)?.collect();
+            assert_eq!(radix.pow(width as u32), codes.len());
+            for pair in codes.windows(2) {
+                let changed: Vec<(char, char)> = pair[0].chars()
+                    .zip(pair[1].chars())
+                    .filter(|(a, b)| a != b)
+                    .collect();
+                assert_eq!(1, changed.len());
+            }
+        }
+        assert!(sequence(1, 3).is_err());
+        Ok(())
+    }
+}
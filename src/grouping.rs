@@ -0,0 +1,243 @@
+//! Digit grouping, e.g. `1111_0000_1010_0101` in nibbles, `1,048,576` in
+//! thousands or `10,48,576` in the Indian lakh/crore style.
+
+use crate::char_to_digit;
+use crate::parse::ParseOptions;
+use crate::script::normalize_digit;
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// The end from which digits are grouped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum GroupDirection {
+    /// Groups are counted from the least significant digit, so that the
+    /// leftmost group may be short.
+    FromRight,
+    /// Groups are counted from the most significant digit, so that the
+    /// rightmost group may be short.
+    FromLeft,
+}
+
+/// The way digits are split into groups by a separator.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Grouping {
+    size: usize,
+    separator: char,
+    direction: GroupDirection,
+    indian: bool,
+}
+
+impl Grouping {
+    /// Group `size` digits, separated by `separator`, from the right.
+    pub fn new(size: usize, separator: char) -> RadixResult<Self> {
+        if size == 0 { return Err(RadixErr::WidthNotSupported(size)); }
+        Ok(Grouping::fixed(size, separator))
+    }
+
+    /// Groups of 4 digits separated by `_`, e.g. for binary.
+    pub fn nibbles() -> Self {
+        Grouping::fixed(4, '_')
+    }
+
+    /// Groups of 8 digits separated by `_`, e.g. for binary.
+    pub fn bytes() -> Self {
+        Grouping::fixed(8, '_')
+    }
+
+    /// Groups of 3 digits separated by `,`.
+    pub fn thousands() -> Self {
+        Grouping::fixed(3, ',')
+    }
+
+    /// The Indian lakh/crore style, where the last 3 digits form a group
+    /// and the digits above them are grouped in pairs, e.g. `1,00,00,000`.
+    pub fn indian(separator: char) -> Self {
+        Grouping {
+            size: 3,
+            separator,
+            direction: GroupDirection::FromRight,
+            indian: true,
+        }
+    }
+
+    /// Use another `separator`.
+    pub fn with_separator(self, separator: char) -> Self {
+        Grouping { separator, ..self }
+    }
+
+    /// Group from the end given by `direction`. The Indian style is
+    /// always grouped from the right.
+    pub fn with_direction(self, direction: GroupDirection) -> Self {
+        Grouping { direction, ..self }
+    }
+
+    pub fn size(&self) -> usize { self.size }
+
+    pub fn separator(&self) -> char { self.separator }
+
+    pub fn direction(&self) -> GroupDirection { self.direction }
+
+    pub fn is_indian(&self) -> bool { self.indian }
+
+    fn fixed(size: usize, separator: char) -> Self {
+        Grouping {
+            size,
+            separator,
+            direction: GroupDirection::FromRight,
+            indian: false,
+        }
+    }
+
+    /// Whether a separator goes before the digit at index `idx` of `len`
+    /// digits, counted from the left.
+    pub(crate) fn separator_before(&self, idx: usize, len: usize) -> bool {
+        let from_right: usize = len - idx;
+        match (self.indian, self.direction) {
+            _ if idx == 0 => false,
+            (true, _) => from_right == 3
+                || (from_right > 3 && (from_right - 3).is_multiple_of(2)),
+            (false, GroupDirection::FromRight) =>
+                from_right.is_multiple_of(self.size),
+            (false, GroupDirection::FromLeft) => idx.is_multiple_of(self.size),
+        }
+    }
+
+    /// The number of separators that `group` inserts into `len` digits.
+    pub(crate) fn separators(&self, len: usize) -> usize {
+        match self.indian {
+            true if len > 3 => 1 + (len - 4) / 2,
+            true => 0,
+            false => len.saturating_sub(1) / self.size,
+        }
+    }
+
+    /// The fewest digits that take up at least `len` chars once grouped.
+    pub(crate) fn digits_filling(&self, len: usize) -> usize {
+        match self.indian {
+            // `1,000` takes 5 chars, and every 2 more digits take 3 more.
+            true if len > 3 => 4 + 2 * (len - 4) / 3,
+            true => len,
+            // Every group after the first takes `size + 1` chars.
+            false => len - len.saturating_sub(1) / (self.size + 1),
+        }
+    }
+
+    /// Insert separators into `digits`.
+    pub fn group(&self, digits: &str) -> String {
+        let len: usize = digits.chars().count();
+        let mut string: String = String::with_capacity(2 * len);
+        for (idx, digit) in digits.chars().enumerate() {
+            if self.separator_before(idx, len) { string.push(self.separator); }
+            string.push(digit);
+        }
+        string
+    }
+
+    /// Parse digits that may contain separators, encoded in a certain
+    /// `radix`. A separator may not lead, trail or follow another one, and
+    /// fails with `RadixErr::IllegalChar` if it is a digit in `radix`.
+    pub fn parse(&self, input: &str, radix: usize) -> RadixResult<RadixNum> {
+        let digit: char = normalize_digit(self.separator).to_ascii_uppercase();
+        if char_to_digit(digit).is_ok_and(|digit| digit < radix) {
+            return Err(RadixErr::IllegalChar(self.separator));
+        }
+        let base: &str = input.trim();
+        let doubled: String = [self.separator; 2].iter().collect();
+        if base.starts_with(self.separator) || base.ends_with(self.separator)
+            || base.contains(&doubled) {
+            return Err(RadixErr::IllegalChar(self.separator));
+        }
+        let options = ParseOptions::new().with_any_separator(self.separator);
+        RadixNum::from_str_in(input, base, radix, &options)
+    }
+}
+
+impl RadixNum {
+    /// The digits, grouped by `grouping`, e.g. `"1111_0000"`.
+    pub fn grouped(&self, grouping: &Grouping) -> String {
+        grouping.group(self.as_str())
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::grouping::*;
+
+    #[test]
+    fn fixed_size() -> RadixResult<()> {
+        let num = RadixNum::from_str("F0A5", 16)?.with_radix(2)?;
+        assert_eq!("1111_0000_1010_0101", num.grouped(&Grouping::nibbles()));
+        assert_eq!("11110000_10100101",   num.grouped(&Grouping::bytes()));
+        let num = RadixNum::from(1048576_u32);
+        assert_eq!("1,048,576", num.grouped(&Grouping::thousands()));
+        assert_eq!("1'048'576",
+                   num.grouped(&Grouping::thousands().with_separator('\'')));
+        let from_left = Grouping::thousands()
+            .with_direction(GroupDirection::FromLeft)
+            .with_separator(' ');
+        assert_eq!("104 857 6", num.grouped(&from_left));
+        let short = RadixNum::from(576_u32);
+        assert_eq!("576", short.grouped(&Grouping::thousands()));
+        assert_eq!(Err(RadixErr::WidthNotSupported(0)), Grouping::new(0, ','));
+        Ok(())
+    }
+
+    #[test]
+    fn indian() {
+        let lakh = Grouping::indian(',');
+        assert_eq!("1,00,00,000", lakh.group("10000000"));
+        assert_eq!("10,48,576",   lakh.group("1048576"));
+        assert_eq!("1,000",       lakh.group("1000"));
+        assert_eq!("999",         lakh.group("999"));
+    }
+
+    #[test]
+    fn grouped_len() -> RadixResult<()> {
+        let groupings = [
+            Grouping::nibbles(),
+            Grouping::thousands().with_direction(GroupDirection::FromLeft),
+            Grouping::new(1, ' ')?,
+            Grouping::indian(','),
+        ];
+        for grouping in groupings.iter() {
+            let len = |digits: usize| digits + grouping.separators(digits);
+            for digits in 0 .. 100 {
+                let grouped: String = grouping.group(&"1".repeat(digits));
+                assert_eq!(grouped.chars().count(), len(digits));
+                let filling: usize = grouping.digits_filling(digits);
+                assert!(len(filling) >= digits);
+                assert!(filling == 0 || len(filling - 1) < digits);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> RadixResult<()> {
+        let nibbles = Grouping::nibbles();
+        assert_eq!("F0A5", nibbles.parse("1111_0000_1010_0101", 2)?
+                   .with_radix(16)?.as_str());
+        let lakh = Grouping::indian(',');
+        assert_eq!("10000000", lakh.parse("1,00,00,000", 10)?.as_str());
+        assert_eq!(Err(RadixErr::IllegalChar('_')), nibbles.parse("_1010", 2));
+        assert_eq!(Err(RadixErr::IllegalChar('_')), nibbles.parse("10__10", 2));
+        let letters = Grouping::new(3, 'A')?;
+        assert_eq!(Err(RadixErr::IllegalChar('A')), letters.parse("1A2", 16));
+        assert_eq!(Err(RadixErr::IllegalChar('A')), letters.parse("1A2", 11));
+        assert_eq!("1002", letters.parse("1A002", 10)?.as_str());
+        assert_eq!(Err(RadixErr::IllegalChar('٣')),
+                   Grouping::new(3, '٣')?.parse("1٣000", 10));
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: " 10_1021".to_string(),
+            position: 6,
+            digits: vec!['2'],
+            radix: 2,
+        }), nibbles.parse(" 10_1021", 2));
+        for &(grouping, radix) in &[(Grouping::bytes(), 2), (lakh, 10)] {
+            let num = RadixNum::from(u64::MAX).with_radix(radix)?;
+            assert_eq!(num, grouping.parse(&num.grouped(&grouping), radix)?);
+        }
+        Ok(())
+    }
+}
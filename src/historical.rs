@@ -0,0 +1,200 @@
+//! Historical positional numerals: Babylonian (base 60) and Mayan (base 20)
+//! numbers, rendered as Unicode glyphs or as ASCII transliterations.
+
+use crate::{mixed_radix_digits, mixed_radix_value};
+use crate::{RadixErr, RadixResult};
+
+/// The Babylonian unit wedge, `𒁹`.
+const BABYLONIAN_ONE: char = '\u{12079}';
+/// The Babylonian ten, `𒌋`.
+const BABYLONIAN_TEN: char = '\u{1230B}';
+/// The late Babylonian placeholder for an empty position, `𒑲`.
+const BABYLONIAN_ZERO: char = '\u{12472}';
+/// The Mayan zero, `𝋠`, followed by the numerals one to nineteen.
+const MAYAN_ZERO: u32 = 0x1D2E0;
+
+/// How a numeral is written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// The glyphs of the Cuneiform or Mayan Numerals Unicode blocks.
+    Glyphs,
+    /// A transliteration with decimal digits, e.g. `1,57,46,40` for a
+    /// Babylonian or `9.12.11.5.18` for a Mayan numeral.
+    Ascii,
+}
+
+/// The radices of the positions of a Mayan numeral.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MayanCount {
+    /// Every position is base 20.
+    Vigesimal,
+    /// The Long Count of the calendar, where the second position is base
+    /// 18, so that the third position counts years of 360 days.
+    Calendar,
+}
+
+impl MayanCount {
+    fn radix(self, position: usize) -> u128 {
+        match (self, position) {
+            (MayanCount::Calendar, 1) => 18,
+            _ => 20,
+        }
+    }
+}
+
+/// Render `number` as a Babylonian numeral. Glyph positions are separated
+/// by spaces.
+pub fn to_babylonian(number: u128, notation: Notation) -> String {
+    let digits: Vec<usize> = digits(number, |_| 60);
+    match notation {
+        Notation::Ascii => join(&digits, ","),
+        Notation::Glyphs => digits.iter()
+            .map(|&digit| match digit {
+                0 => BABYLONIAN_ZERO.to_string(),
+                _ => repeat(BABYLONIAN_TEN, digit / 10)
+                    + &repeat(BABYLONIAN_ONE, digit % 10),
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+    }
+}
+
+/// Parse a Babylonian numeral, in either notation.
+pub fn from_babylonian(input: &str) -> RadixResult<u128> {
+    let input: &str = input.trim();
+    let digits: Vec<usize> = if input.is_ascii() {
+        split_ascii(input, ',')?
+    } else {
+        input.split_whitespace().map(|position| {
+            let mut digit: usize = 0;
+            for c in position.chars() {
+                digit += match c {
+                    BABYLONIAN_ONE => 1,
+                    BABYLONIAN_TEN => 10,
+                    BABYLONIAN_ZERO if position.chars().count() == 1 => 0,
+                    c => return Err(RadixErr::IllegalChar(c)),
+                };
+            }
+            Ok(digit)
+        }).collect::<RadixResult<Vec<usize>>>()?
+    };
+    value(&digits, |_| 60)
+}
+
+/// Render `number` as a Mayan numeral, most significant position first.
+pub fn to_mayan(
+    number: u128,
+    count: MayanCount,
+    notation: Notation,
+) -> String {
+    let digits: Vec<usize> = digits(number, |idx| count.radix(idx));
+    match notation {
+        Notation::Ascii => join(&digits, "."),
+        Notation::Glyphs => digits.iter()
+            .filter_map(|&digit| char::from_u32(MAYAN_ZERO + digit as u32))
+            .collect(),
+    }
+}
+
+/// Parse a Mayan numeral, in either notation.
+pub fn from_mayan(input: &str, count: MayanCount) -> RadixResult<u128> {
+    let input: &str = input.trim();
+    let digits: Vec<usize> = if input.is_ascii() {
+        split_ascii(input, '.')?
+    } else {
+        input.chars().map(|c| match c as u32 {
+            code @ MAYAN_ZERO ..= 0x1D2F3 => Ok((code - MAYAN_ZERO) as usize),
+            _ => Err(RadixErr::IllegalChar(c)),
+        }).collect::<RadixResult<Vec<usize>>>()?
+    };
+    value(&digits, |idx| count.radix(idx))
+}
+
+/// The digits of `number`, most significant first, and a single zero
+/// digit for zero.
+fn digits<F>(number: u128, radix_at: F) -> Vec<usize>
+where F: Fn(usize) -> u128 {
+    let mut digits: Vec<usize> = mixed_radix_digits(number, radix_at);
+    if digits.is_empty() { digits.push(0); }
+    digits.reverse();
+    digits
+}
+
+/// The value of `digits`, most significant first.
+fn value<F>(digits: &[usize], radix_at: F) -> RadixResult<u128>
+where F: Fn(usize) -> u128 {
+    if digits.is_empty() { return Err(RadixErr::EmptyInput); }
+    let digits: Vec<usize> = digits.iter().rev().cloned().collect();
+    mixed_radix_value(&digits, radix_at)
+}
+
+fn split_ascii(input: &str, separator: char) -> RadixResult<Vec<usize>> {
+    if input.is_empty() { return Err(RadixErr::EmptyInput); }
+    input.split(separator).map(|position| {
+        let position: &str = position.trim();
+        if let Some(c) = position.chars().find(|c| !c.is_ascii_digit()) {
+            return Err(RadixErr::IllegalChar(c));
+        }
+        position.parse().map_err(|_| RadixErr::EmptyInput)
+    }).collect()
+}
+
+fn join(digits: &[usize], separator: &str) -> String {
+    digits.iter()
+        .map(|digit| digit.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+fn repeat(c: char, count: usize) -> String {
+    std::iter::repeat_n(c, count).collect()
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::historical::*;
+
+    #[test]
+    fn babylonian() -> RadixResult<()> {
+        assert_eq!("1,57,46,40", to_babylonian(424000, Notation::Ascii));
+        assert_eq!("0",          to_babylonian(0, Notation::Ascii));
+        // The digits are 1, 20, 0 and 32.
+        let n: u128 = 216000 + 20 * 3600 + 32;
+        assert_eq!("𒁹 𒌋𒌋 𒑲 𒌋𒌋𒌋𒁹𒁹", to_babylonian(n, Notation::Glyphs));
+        assert_eq!(424000, from_babylonian("1,57,46,40")?);
+        for &n in &[0, 1, 59, 60, 3600, 424000, u128::MAX] {
+            let glyphs = to_babylonian(n, Notation::Glyphs);
+            assert_eq!(n, from_babylonian(&glyphs)?);
+            assert_eq!(n, from_babylonian(&to_babylonian(n, Notation::Ascii))?);
+        }
+        assert_eq!(Err(RadixErr::ValueOutOfRange(60)), from_babylonian("1,60"));
+        assert_eq!(Err(RadixErr::IllegalChar('x')), from_babylonian("1,x"));
+        assert_eq!(Err(RadixErr::IllegalChar('A')), from_babylonian("𒁹A"));
+        Ok(())
+    }
+
+    #[test]
+    fn mayan() -> RadixResult<()> {
+        // 9.12.11.5.18 is a Long Count date, in days.
+        let days: u128 = (((9 * 20 + 12) * 20 + 11) * 18 + 5) * 20 + 18;
+        let calendar = MayanCount::Calendar;
+        assert_eq!("9.12.11.5.18", to_mayan(days, calendar, Notation::Ascii));
+        assert_eq!(days, from_mayan("9.12.11.5.18", calendar)?);
+        assert_eq!("1.0", to_mayan(20, MayanCount::Vigesimal, Notation::Ascii));
+        assert_eq!("1.0.0", to_mayan(360, calendar, Notation::Ascii));
+        assert_eq!("𝋡𝋠", to_mayan(20, MayanCount::Vigesimal, Notation::Glyphs));
+        for &count in &[MayanCount::Vigesimal, MayanCount::Calendar] {
+            for &n in &[0, 19, 20, 359, 360, days, u128::MAX] {
+                let glyphs = to_mayan(n, count, Notation::Glyphs);
+                assert_eq!(n, from_mayan(&glyphs, count)?);
+            }
+        }
+        assert_eq!(Err(RadixErr::ValueOutOfRange(18)),
+                   from_mayan("1.18.0", calendar));
+        assert_eq!(Err(RadixErr::IllegalChar('x')),
+                   from_mayan("𝋡x", calendar));
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
 //! A rust library to deal with number conversion between radices.
 
+use std::cmp;
+use std::convert::TryFrom;
 use std::error;
 use std::fmt;
+use std::str;
 
 const DEBUG: bool = false;
 
@@ -15,11 +18,30 @@ macro_rules! debug {
     }}
 }
 
-
+pub mod asm;
+pub mod bcd;
+pub mod beta;
+pub mod combinadic;
+pub mod detect;
+pub mod dialect;
+pub mod fibonacci;
+pub mod fixed_width;
+pub mod format;
+pub mod gray;
+pub mod grouping;
+pub mod historical;
+pub mod naf;
+pub mod padic;
+pub mod parse;
+pub mod rns;
+pub mod roman;
+pub mod script;
+pub mod sexagesimal;
 
 pub type RadixResult<T> = Result<T, RadixErr>;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum RadixErr {
     RadixNotSupported(usize),
     EmptyInput,
@@ -27,7 +49,29 @@ pub enum RadixErr {
     FailedToUppercase,
     IllegalChar(char),
     IllegalDigit(usize),
-    InvalidDigit { digit: char, radix: usize },
+    /// The `input` has digits that are not valid in `radix`. The
+    /// `position` is the byte offset of the first one in `input`, and
+    /// `digits` lists every one of them in order.
+    InvalidDigit {
+        input: String,
+        position: usize,
+        digits: Vec<char>,
+        radix: usize,
+    },
+    ArithmeticOverflow,
+    /// The value of `input`, encoded in `radix`, exceeds `limit`, the
+    /// largest value of the target integer type.
+    Overflow { input: String, radix: usize, limit: u128 },
+    InvalidSubset(usize),
+    ValueOutOfRange(u128),
+    WidthNotSupported(usize),
+    WidthExceeded { width: usize, radix: usize },
+    NotCoprime(u64, u64),
+    InsufficientRange { range: u128, needed: u128 },
+    MismatchedModuli,
+    UnexpectedEndOfInput,
+    BaseNotSupported,
+    NotFinite,
 }
 
 impl error::Error for RadixErr {
@@ -40,6 +84,18 @@ impl error::Error for RadixErr {
             RadixErr::IllegalChar(_) => "Illegal char",
             RadixErr::IllegalDigit(_) => "Illegal digit",
             RadixErr::InvalidDigit{..} => "Invalid digit",
+            RadixErr::ArithmeticOverflow => "Arithmetic overflow",
+            RadixErr::Overflow{..} => "Overflow",
+            RadixErr::InvalidSubset(_) => "Invalid subset",
+            RadixErr::ValueOutOfRange(_) => "Value out of range",
+            RadixErr::WidthNotSupported(_) => "Width not supported",
+            RadixErr::WidthExceeded{..} => "Width exceeded",
+            RadixErr::NotCoprime(..) => "Moduli not coprime",
+            RadixErr::InsufficientRange{..} => "Insufficient range",
+            RadixErr::MismatchedModuli => "Mismatched moduli",
+            RadixErr::UnexpectedEndOfInput => "Unexpected end of input",
+            RadixErr::BaseNotSupported => "Base not supported",
+            RadixErr::NotFinite => "Not finite",
         }
     }
 
@@ -61,8 +117,46 @@ impl fmt::Display for RadixErr {
                 write!(f, "Illegal character: {}", &c),
             RadixErr::IllegalDigit(ref us) =>
                 write!(f, "Illegal digit: {}", &us),
-            RadixErr::InvalidDigit{digit: c, radix: us} =>
-                write!(f, "Invalid digit: {} {}", &c, &us),
+            RadixErr::InvalidDigit{ref input, position, ref digits, radix} => {
+                let digits: Vec<String> = digits.iter()
+                    .map(|digit| format!("{:?}", digit))
+                    .collect();
+                // The caret is aligned by chars, not bytes, and by bytes if
+                // `position` is not a char boundary of `input`.
+                let column: usize = input.get(.. position)
+                    .map_or(position, |s| s.chars().count());
+                writeln!(f, "Invalid digit{} {} for radix {} at byte {}",
+                         if digits.len() == 1 { "" } else { "s" },
+                         digits.join(", "), radix, position)?;
+                write!(f, "  {}\n  {:>2$}", input, "^", column + 1)
+            },
+            RadixErr::ArithmeticOverflow =>
+                write!(f, "Arithmetic overflow"),
+            RadixErr::Overflow{ref input, radix, limit} =>
+                write!(f, "Value of {} in radix {} exceeds the limit {}",
+                       &input, &radix, &limit),
+            RadixErr::InvalidSubset(ref us) =>
+                write!(f, "Invalid subset element: {}", &us),
+            RadixErr::ValueOutOfRange(ref value) =>
+                write!(f, "Value out of range: {}", &value),
+            RadixErr::WidthNotSupported(ref us) =>
+                write!(f, "Width not supported: {}", &us),
+            RadixErr::WidthExceeded{width, radix} =>
+                write!(f, "Value does not fit in {} digits of radix {}",
+                       &width, &radix),
+            RadixErr::NotCoprime(ref a, ref b) =>
+                write!(f, "Moduli not coprime: {} {}", &a, &b),
+            RadixErr::InsufficientRange{range, needed} =>
+                write!(f, "Range {} is smaller than the needed {}",
+                       &range, &needed),
+            RadixErr::MismatchedModuli =>
+                write!(f, "Residues have mismatched moduli"),
+            RadixErr::UnexpectedEndOfInput =>
+                write!(f, "Unexpected end of input"),
+            RadixErr::BaseNotSupported =>
+                write!(f, "Base not supported, it must lie in (1, 36]"),
+            RadixErr::NotFinite =>
+                write!(f, "Value is not a finite number"),
         }
     }
 }
@@ -71,57 +165,78 @@ const MAX_RADIX: usize = 36;
 const MIN_RADIX: usize = 2;
 
 fn is_radix_valid(radix: usize) -> bool {
-    MIN_RADIX <= radix && radix <= MAX_RADIX
+    (MIN_RADIX..=MAX_RADIX).contains(&radix)
+}
+
+
+/// A number in some radix, with an optional fixed width. The
+/// representation is opaque: a `RadixNum` is made by `from_str`, `From` or
+/// `str::parse` and changed by `with_radix` and `with_width`.
+#[derive(Clone, Debug)]
+pub struct RadixNum {
+    repr: Repr,
+    width: Option<Width>,
 }
 
+/// The digits of a `RadixNum`, in the variant for their radix.
+#[derive(Clone, Debug)]
+enum Repr {
+    Radix2(String),
+    Radix3(String),
+    Radix4(String),
+    Radix5(String),
+    Radix6(String),
+    Radix7(String),
+    Radix8(String),
+    Radix9(String),
+    Radix10(String),
+    Radix11(String),
+    Radix12(String),
+    Radix13(String),
+    Radix14(String),
+    Radix15(String),
+    Radix16(String),
+    Radix17(String),
+    Radix18(String),
+    Radix19(String),
+    Radix20(String),
+    Radix21(String),
+    Radix22(String),
+    Radix23(String),
+    Radix24(String),
+    Radix25(String),
+    Radix26(String),
+    Radix27(String),
+    Radix28(String),
+    Radix29(String),
+    Radix30(String),
+    Radix31(String),
+    Radix32(String),
+    Radix33(String),
+    Radix34(String),
+    Radix35(String),
+    Radix36(String),
+}
 
-/// A number in some radix.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub enum RadixNum {
-    #[doc(hidden)] Radix2(String),
-    #[doc(hidden)] Radix3(String),
-    #[doc(hidden)] Radix4(String),
-    #[doc(hidden)] Radix5(String),
-    #[doc(hidden)] Radix6(String),
-    #[doc(hidden)] Radix7(String),
-    #[doc(hidden)] Radix8(String),
-    #[doc(hidden)] Radix9(String),
-    #[doc(hidden)] Radix10(String),
-    #[doc(hidden)] Radix11(String),
-    #[doc(hidden)] Radix12(String),
-    #[doc(hidden)] Radix13(String),
-    #[doc(hidden)] Radix14(String),
-    #[doc(hidden)] Radix15(String),
-    #[doc(hidden)] Radix16(String),
-    #[doc(hidden)] Radix17(String),
-    #[doc(hidden)] Radix18(String),
-    #[doc(hidden)] Radix19(String),
-    #[doc(hidden)] Radix20(String),
-    #[doc(hidden)] Radix21(String),
-    #[doc(hidden)] Radix22(String),
-    #[doc(hidden)] Radix23(String),
-    #[doc(hidden)] Radix24(String),
-    #[doc(hidden)] Radix25(String),
-    #[doc(hidden)] Radix26(String),
-    #[doc(hidden)] Radix27(String),
-    #[doc(hidden)] Radix28(String),
-    #[doc(hidden)] Radix29(String),
-    #[doc(hidden)] Radix30(String),
-    #[doc(hidden)] Radix31(String),
-    #[doc(hidden)] Radix32(String),
-    #[doc(hidden)] Radix33(String),
-    #[doc(hidden)] Radix34(String),
-    #[doc(hidden)] Radix35(String),
-    #[doc(hidden)] Radix36(String),
+/// A fixed width of `digits`, in the `radix` it was set in.
+#[derive(Copy, Clone, Debug)]
+struct Width {
+    digits: usize,
+    radix: usize,
+}
+
+impl Width {
+    /// The number of digits in `radix` that holds any value of this width.
+    fn scaled(self, radix: usize) -> usize {
+        scale_width(self.digits, self.radix, radix)
+    }
 }
 
 impl RadixNum {
-    /// Convert a `base` encoded in a certain `radix` to a `RadixNum`.
+    /// Convert a `base` encoded in a certain `radix` to a `RadixNum`. A
+    /// `base` with leading zeros, e.g. `"00FF"`, keeps them as its width.
     pub fn from_str(base: &str, radix: usize) -> RadixResult<Self> {
-        Self::validate_radix(radix)?;
-        let base: String = Self::validate_base(&base, radix)?;
-        let decimal: String = Self::radix_x_to_dec(&base, radix)?.to_string();
-        RadixNum::Radix10(decimal).with_radix(radix)
+        Self::from_str_with(base, radix, &parse::ParseOptions::new())
     }
 
     #[inline(always)]
@@ -134,189 +249,317 @@ impl RadixNum {
 
     #[inline(always)]
     fn validate_base(base: &str, radix: usize) -> RadixResult<String> {
+        Self::validate_base_with(base, radix, &parse::ParseOptions::new())
+    }
+
+    /// Validate `base` and strip it down to its upper case digits, skipping
+    /// the separators that `options` allow.
+    fn validate_base_with(
+        base: &str,
+        radix: usize,
+        options: &parse::ParseOptions,
+    ) -> RadixResult<String> {
+        let input: &str = base;
         let base: &str = base.trim();
         if base.is_empty() { return Err(RadixErr::EmptyInput); }
-        let base: String = base.to_uppercase();
-        let is_valid_digit = |d| {
-            let x = '0' <= d && d <= '9';
-            let y = 'A' <= d && d <= ('A' as usize + radix - 10) as u8 as char;
-            x || y
+        let offset: usize = input.len() - input.trim_start().len();
+        if !options.edge_separators() {
+            let edges = base.chars().next().into_iter()
+                .chain(base.chars().next_back());
+            for c in edges {
+                if options.is_separator(c) {
+                    return Err(RadixErr::IllegalChar(c));
+                }
+            }
+        }
+        let is_valid_digit = |d: char| {
+            char_to_digit(d).is_ok_and(|digit| digit < radix)
         };
-        for digit in base.chars() { if !is_valid_digit(digit) {
-            return Err(RadixErr::InvalidDigit { digit, radix });
-        }}
-        Ok(base)
+        let mut digits: String = String::with_capacity(base.len());
+        let mut invalid: Vec<(usize, char)> = Vec::new();
+        for (idx, c) in base.char_indices() {
+            if options.is_separator(c) { continue; }
+            let digit: char = script::normalize_digit(c);
+            if options.accepts_case(digit)
+                && is_valid_digit(digit.to_ascii_uppercase()) {
+                digits.push(digit.to_ascii_uppercase());
+            } else {
+                invalid.push((offset + idx, c));
+            }
+        }
+        if let Some(&(position, _)) = invalid.first() {
+            return Err(RadixErr::InvalidDigit {
+                input: input.to_string(),
+                position,
+                digits: invalid.into_iter().map(|(_, c)| c).collect(),
+                radix,
+            });
+        }
+        if digits.is_empty() { return Err(RadixErr::EmptyInput); }
+        Ok(digits)
     }
 
     pub fn as_str(&self) -> &str {
-        match *self {
-            RadixNum::Radix2(ref string) |
-            RadixNum::Radix3(ref string) |
-            RadixNum::Radix4(ref string) |
-            RadixNum::Radix5(ref string) |
-            RadixNum::Radix6(ref string) |
-            RadixNum::Radix7(ref string) |
-            RadixNum::Radix8(ref string) |
-            RadixNum::Radix9(ref string) |
-            RadixNum::Radix10(ref string) |
-            RadixNum::Radix11(ref string) |
-            RadixNum::Radix12(ref string) |
-            RadixNum::Radix13(ref string) |
-            RadixNum::Radix14(ref string) |
-            RadixNum::Radix15(ref string) |
-            RadixNum::Radix16(ref string) |
-            RadixNum::Radix17(ref string) |
-            RadixNum::Radix18(ref string) |
-            RadixNum::Radix19(ref string) |
-            RadixNum::Radix20(ref string) |
-            RadixNum::Radix21(ref string) |
-            RadixNum::Radix22(ref string) |
-            RadixNum::Radix23(ref string) |
-            RadixNum::Radix24(ref string) |
-            RadixNum::Radix25(ref string) |
-            RadixNum::Radix26(ref string) |
-            RadixNum::Radix27(ref string) |
-            RadixNum::Radix28(ref string) |
-            RadixNum::Radix29(ref string) |
-            RadixNum::Radix30(ref string) |
-            RadixNum::Radix31(ref string) |
-            RadixNum::Radix32(ref string) |
-            RadixNum::Radix33(ref string) |
-            RadixNum::Radix34(ref string) |
-            RadixNum::Radix35(ref string) |
-            RadixNum::Radix36(ref string) => &string,
+        match self.repr {
+            Repr::Radix2(ref string) |
+            Repr::Radix3(ref string) |
+            Repr::Radix4(ref string) |
+            Repr::Radix5(ref string) |
+            Repr::Radix6(ref string) |
+            Repr::Radix7(ref string) |
+            Repr::Radix8(ref string) |
+            Repr::Radix9(ref string) |
+            Repr::Radix10(ref string) |
+            Repr::Radix11(ref string) |
+            Repr::Radix12(ref string) |
+            Repr::Radix13(ref string) |
+            Repr::Radix14(ref string) |
+            Repr::Radix15(ref string) |
+            Repr::Radix16(ref string) |
+            Repr::Radix17(ref string) |
+            Repr::Radix18(ref string) |
+            Repr::Radix19(ref string) |
+            Repr::Radix20(ref string) |
+            Repr::Radix21(ref string) |
+            Repr::Radix22(ref string) |
+            Repr::Radix23(ref string) |
+            Repr::Radix24(ref string) |
+            Repr::Radix25(ref string) |
+            Repr::Radix26(ref string) |
+            Repr::Radix27(ref string) |
+            Repr::Radix28(ref string) |
+            Repr::Radix29(ref string) |
+            Repr::Radix30(ref string) |
+            Repr::Radix31(ref string) |
+            Repr::Radix32(ref string) |
+            Repr::Radix33(ref string) |
+            Repr::Radix34(ref string) |
+            Repr::Radix35(ref string) |
+            Repr::Radix36(ref string) => string,
         }
     }
 
     /// Change the radix that `self` is encoded with. This does not change
-    /// the represented value, but it does change its representation.
+    /// the represented value, but it does change its representation. The
+    /// digits are converted directly, so the value may be of any size. A
+    /// width is scaled to the digits needed for any value of that width,
+    /// e.g. 8 hex digits become 32 binary digits.
     pub fn with_radix(&self, radix: usize) -> RadixResult<Self> {
-        let digits_radix_x: String =
-            Self::dec_to_radix_x(self.as_decimal()?, radix)?;
-        Ok(match radix {
-             2 => RadixNum::Radix2(digits_radix_x),
-             3 => RadixNum::Radix3(digits_radix_x),
-             4 => RadixNum::Radix4(digits_radix_x),
-             5 => RadixNum::Radix5(digits_radix_x),
-             6 => RadixNum::Radix6(digits_radix_x),
-             7 => RadixNum::Radix7(digits_radix_x),
-             8 => RadixNum::Radix8(digits_radix_x),
-             9 => RadixNum::Radix9(digits_radix_x),
-            10 => RadixNum::Radix10(digits_radix_x),
-            11 => RadixNum::Radix11(digits_radix_x),
-            12 => RadixNum::Radix12(digits_radix_x),
-            13 => RadixNum::Radix13(digits_radix_x),
-            14 => RadixNum::Radix14(digits_radix_x),
-            15 => RadixNum::Radix15(digits_radix_x),
-            16 => RadixNum::Radix16(digits_radix_x),
-            17 => RadixNum::Radix17(digits_radix_x),
-            18 => RadixNum::Radix18(digits_radix_x),
-            19 => RadixNum::Radix19(digits_radix_x),
-            20 => RadixNum::Radix20(digits_radix_x),
-            21 => RadixNum::Radix21(digits_radix_x),
-            22 => RadixNum::Radix22(digits_radix_x),
-            23 => RadixNum::Radix23(digits_radix_x),
-            24 => RadixNum::Radix24(digits_radix_x),
-            25 => RadixNum::Radix25(digits_radix_x),
-            26 => RadixNum::Radix26(digits_radix_x),
-            27 => RadixNum::Radix27(digits_radix_x),
-            28 => RadixNum::Radix28(digits_radix_x),
-            29 => RadixNum::Radix29(digits_radix_x),
-            30 => RadixNum::Radix30(digits_radix_x),
-            31 => RadixNum::Radix31(digits_radix_x),
-            32 => RadixNum::Radix32(digits_radix_x),
-            33 => RadixNum::Radix33(digits_radix_x),
-            34 => RadixNum::Radix34(digits_radix_x),
-            35 => RadixNum::Radix35(digits_radix_x),
-            36 => RadixNum::Radix36(digits_radix_x),
+        Self::validate_radix(radix)?;
+        if radix == self.radix() { return Ok(self.clone()); }
+        let digits: Vec<usize> = self.digits()
+            .map(char_to_digit)
+            .collect::<RadixResult<Vec<usize>>>()?;
+        let converted: String = convert_digits(&digits, self.radix(), radix)
+            .into_iter()
+            .map(digit_to_char)
+            .collect::<RadixResult<String>>()?;
+        let num: RadixNum = Self::from_digits(converted, radix)?;
+        match self.width {
+            Some(origin) => num.padded(origin.scaled(radix), origin),
+            None => Ok(num),
+        }
+    }
+
+    /// Pad the digits with leading zeros to a fixed `width`, which is kept
+    /// across `with_radix`. Fails with `RadixErr::WidthExceeded` if the
+    /// value needs more than `width` digits.
+    pub fn with_width(&self, width: usize) -> RadixResult<Self> {
+        if width == 0 { return Err(RadixErr::WidthNotSupported(width)); }
+        self.padded(width, Width { digits: width, radix: self.radix() })
+    }
+
+    /// Pad the digits to `width`, which was scaled from `origin`.
+    fn padded(&self, width: usize, origin: Width) -> RadixResult<Self> {
+        let digits: &str = self.as_str().trim_start_matches('0');
+        let radix: usize = self.radix();
+        if digits.len() > width {
+            return Err(RadixErr::WidthExceeded { width, radix });
+        }
+        let padded: String = format!("{:0>1$}", digits, width);
+        Self::from_parts(padded, radix, Some(origin))
+    }
+
+    /// Drop the fixed width, along with any leading zeros.
+    pub fn without_width(&self) -> Self {
+        let digits: &str = self.as_str().trim_start_matches('0');
+        let digits: &str = if digits.is_empty() { "0" } else { digits };
+        // The radix is known to be valid.
+        Self::from_parts(digits.to_string(), self.radix(), None)
+            .unwrap_or_else(|_| self.clone())
+    }
+
+    /// Encode a decimal `number` in a certain `radix`.
+    fn from_decimal(number: u128, radix: usize) -> RadixResult<Self> {
+        let digits_radix_x: String = Self::dec_to_radix_x(number, radix)?;
+        Self::from_digits(digits_radix_x, radix)
+    }
+
+    /// Wrap already validated `digits_radix_x` in the variant for `radix`.
+    /// Leading zeros, e.g. in `"00FF"`, are kept as its width.
+    fn from_digits(digits_radix_x: String, radix: usize)
+        -> RadixResult<Self> {
+        let len: usize = digits_radix_x.len();
+        let width: Option<Width> = Some(Width { digits: len, radix })
+            .filter(|_| len > 1 && digits_radix_x.starts_with('0'));
+        Self::from_parts(digits_radix_x, radix, width)
+    }
+
+    fn from_parts(
+        digits_radix_x: String,
+        radix: usize,
+        width: Option<Width>,
+    ) -> RadixResult<Self> {
+        let repr: Repr = match radix {
+             2 => Repr::Radix2(digits_radix_x),
+             3 => Repr::Radix3(digits_radix_x),
+             4 => Repr::Radix4(digits_radix_x),
+             5 => Repr::Radix5(digits_radix_x),
+             6 => Repr::Radix6(digits_radix_x),
+             7 => Repr::Radix7(digits_radix_x),
+             8 => Repr::Radix8(digits_radix_x),
+             9 => Repr::Radix9(digits_radix_x),
+            10 => Repr::Radix10(digits_radix_x),
+            11 => Repr::Radix11(digits_radix_x),
+            12 => Repr::Radix12(digits_radix_x),
+            13 => Repr::Radix13(digits_radix_x),
+            14 => Repr::Radix14(digits_radix_x),
+            15 => Repr::Radix15(digits_radix_x),
+            16 => Repr::Radix16(digits_radix_x),
+            17 => Repr::Radix17(digits_radix_x),
+            18 => Repr::Radix18(digits_radix_x),
+            19 => Repr::Radix19(digits_radix_x),
+            20 => Repr::Radix20(digits_radix_x),
+            21 => Repr::Radix21(digits_radix_x),
+            22 => Repr::Radix22(digits_radix_x),
+            23 => Repr::Radix23(digits_radix_x),
+            24 => Repr::Radix24(digits_radix_x),
+            25 => Repr::Radix25(digits_radix_x),
+            26 => Repr::Radix26(digits_radix_x),
+            27 => Repr::Radix27(digits_radix_x),
+            28 => Repr::Radix28(digits_radix_x),
+            29 => Repr::Radix29(digits_radix_x),
+            30 => Repr::Radix30(digits_radix_x),
+            31 => Repr::Radix31(digits_radix_x),
+            32 => Repr::Radix32(digits_radix_x),
+            33 => Repr::Radix33(digits_radix_x),
+            34 => Repr::Radix34(digits_radix_x),
+            35 => Repr::Radix35(digits_radix_x),
+            36 => Repr::Radix36(digits_radix_x),
             radix => return Err(RadixErr::RadixNotSupported(radix)),
-        })
+        };
+        Ok(RadixNum { repr, width })
+    }
+
+    /// The fixed number of digits, if any, set by leading zeros in
+    /// `from_str` or by `with_width`. It is scaled from the radix it was
+    /// set in, so changing the radix back restores it. The width takes no
+    /// part in comparisons, e.g. `007` equals `7` in radix 8.
+    pub fn width(&self) -> Option<usize> {
+        self.width.map(|origin| origin.scaled(self.radix()))
     }
 
     /// Retrieve the radix that `self` is encoded with.
     pub fn radix(&self) -> usize {
-        match *self {
-            RadixNum::Radix2(_) => 2,
-            RadixNum::Radix3(_) => 3,
-            RadixNum::Radix4(_) => 4,
-            RadixNum::Radix5(_) => 5,
-            RadixNum::Radix6(_) => 6,
-            RadixNum::Radix7(_) => 7,
-            RadixNum::Radix8(_) => 8,
-            RadixNum::Radix9(_) => 9,
-            RadixNum::Radix10(_) => 10,
-            RadixNum::Radix11(_) => 11,
-            RadixNum::Radix12(_) => 12,
-            RadixNum::Radix13(_) => 13,
-            RadixNum::Radix14(_) => 14,
-            RadixNum::Radix15(_) => 15,
-            RadixNum::Radix16(_) => 16,
-            RadixNum::Radix17(_) => 17,
-            RadixNum::Radix18(_) => 18,
-            RadixNum::Radix19(_) => 19,
-            RadixNum::Radix20(_) => 20,
-            RadixNum::Radix21(_) => 21,
-            RadixNum::Radix22(_) => 22,
-            RadixNum::Radix23(_) => 23,
-            RadixNum::Radix24(_) => 24,
-            RadixNum::Radix25(_) => 25,
-            RadixNum::Radix26(_) => 26,
-            RadixNum::Radix27(_) => 27,
-            RadixNum::Radix28(_) => 28,
-            RadixNum::Radix29(_) => 29,
-            RadixNum::Radix30(_) => 30,
-            RadixNum::Radix31(_) => 31,
-            RadixNum::Radix32(_) => 32,
-            RadixNum::Radix33(_) => 33,
-            RadixNum::Radix34(_) => 34,
-            RadixNum::Radix35(_) => 35,
-            RadixNum::Radix36(_) => 36,
+        match self.repr {
+            Repr::Radix2(_) => 2,
+            Repr::Radix3(_) => 3,
+            Repr::Radix4(_) => 4,
+            Repr::Radix5(_) => 5,
+            Repr::Radix6(_) => 6,
+            Repr::Radix7(_) => 7,
+            Repr::Radix8(_) => 8,
+            Repr::Radix9(_) => 9,
+            Repr::Radix10(_) => 10,
+            Repr::Radix11(_) => 11,
+            Repr::Radix12(_) => 12,
+            Repr::Radix13(_) => 13,
+            Repr::Radix14(_) => 14,
+            Repr::Radix15(_) => 15,
+            Repr::Radix16(_) => 16,
+            Repr::Radix17(_) => 17,
+            Repr::Radix18(_) => 18,
+            Repr::Radix19(_) => 19,
+            Repr::Radix20(_) => 20,
+            Repr::Radix21(_) => 21,
+            Repr::Radix22(_) => 22,
+            Repr::Radix23(_) => 23,
+            Repr::Radix24(_) => 24,
+            Repr::Radix25(_) => 25,
+            Repr::Radix26(_) => 26,
+            Repr::Radix27(_) => 27,
+            Repr::Radix28(_) => 28,
+            Repr::Radix29(_) => 29,
+            Repr::Radix30(_) => 30,
+            Repr::Radix31(_) => 31,
+            Repr::Radix32(_) => 32,
+            Repr::Radix33(_) => 33,
+            Repr::Radix34(_) => 34,
+            Repr::Radix35(_) => 35,
+            Repr::Radix36(_) => 36,
         }
     }
 
+    /// The value as a `usize`, or `RadixErr::Overflow` if it doesn't fit.
     pub fn as_decimal(&self) -> RadixResult<usize> {
         Self::radix_x_to_dec(self.as_str(), self.radix())
     }
 
+    /// Like `as_decimal`, but for values that don't fit in a `usize`.
+    pub fn as_u128(&self) -> RadixResult<u128> {
+        Self::radix_x_to_u128(self.as_str(), self.radix())
+    }
+
+    /// Like `as_decimal`, but wraps around on overflow, i.e. the value
+    /// modulo `usize::MAX + 1`.
+    pub fn as_decimal_wrapping(&self) -> RadixResult<usize> {
+        self.as_u128_wrapping().map(|value| value as usize)
+    }
+
+    /// Like `as_decimal`, but saturates at `usize::MAX` on overflow.
+    pub fn as_decimal_saturating(&self) -> RadixResult<usize> {
+        let value: u128 = self.as_u128_saturating()?;
+        Ok(usize::try_from(value).unwrap_or(usize::MAX))
+    }
+
+    /// Like `as_u128`, but wraps around on overflow, i.e. the value modulo
+    /// `u128::MAX + 1`.
+    pub fn as_u128_wrapping(&self) -> RadixResult<u128> {
+        let radix: u128 = self.radix() as u128;
+        Self::horner(self.as_str(), self.radix(), u128::MAX, |value, digit| {
+            Some(value.wrapping_mul(radix).wrapping_add(digit))
+        })
+    }
+
+    /// Like `as_u128`, but saturates at `u128::MAX` on overflow.
+    pub fn as_u128_saturating(&self) -> RadixResult<u128> {
+        let radix: u128 = self.radix() as u128;
+        Self::horner(self.as_str(), self.radix(), u128::MAX, |value, digit| {
+            Some(value.saturating_mul(radix).saturating_add(digit))
+        })
+    }
+
     pub fn digits<'c>(&'c self) -> impl Iterator<Item=char> + 'c {
         self.as_str().chars()
     }
 
-    fn dec_to_radix_x(number: usize, radix: usize) -> RadixResult<String> {
+    fn dec_to_radix_x(number: u128, radix: usize) -> RadixResult<String> {
         Self::validate_radix(radix)?;
         if number == 0 { return Ok(String::from("0")) }
 
-        let mut number: usize = number;
         let mut stack: Vec<char> = vec![];
-        let get_offset = |digit: usize| -> RadixResult<u8> {
-            match digit {
-                0 ..= 9 => Ok('0' as u8), //  1u8 => '1',   2u8 =>  '2',  etc
-                10 ..= 36 => Ok(55),      // 10u8 => 'A',  11u8 =>  'B',  etc
-                d => Err(RadixErr::IllegalDigit(d)),
-            }
-        };
-
         debug!("\n");
         debug!("[dec_to_radix_x] radix:   {:?}", radix);
         debug!("[dec_to_radix_x] number: {:?}", number);
         debug!("[dec_to_radix_x] stack: {:?}", stack);
 
         debug!("[dec_to_radix_x] loop:");
-        while number > 0 {
-            let digit: usize = number / radix;
-            debug!("[dec_to_radix_x] digit: {}", digit);
-            let remainder: usize = modulus(number, radix);
+        for remainder in mixed_radix_digits(number, |_| radix as u128) {
             debug!("[dec_to_radix_x] remainder: {}", remainder);
-            number = number / radix;
-            debug!("[dec_to_radix_x] number = {}", number);
-            let offset: usize = get_offset(remainder)? as usize;
-            debug!("[dec_to_radix_x] offset: {}", offset);
-            let target_digit: char = (remainder + offset) as u8 as char;
+            let target_digit: char = digit_to_char(remainder)?;
             stack.push(target_digit);
             debug!("[dec_to_radix_x] pushed remainder to stack");
             debug!("[dec_to_radix_x]   stack:  {:?}", stack);
-            debug!("[dec_to_radix_x]   number: {:?}", number);
-            debug!("[dec_to_radix_x]   digit:  {:?}", digit);
             debug!("[dec_to_radix_x]   target digit:  {:?}", target_digit);
         }
 
@@ -330,88 +573,336 @@ impl RadixNum {
     }
 
     fn radix_x_to_dec(base: &str, radix: usize) -> RadixResult<usize> {
+        let limit: u128 = usize::MAX as u128;
+        let value: u128 = Self::radix_x_to_limit(base, radix, limit)?;
+        Ok(value as usize)
+    }
+
+    fn radix_x_to_u128(base: &str, radix: usize) -> RadixResult<u128> {
+        Self::radix_x_to_limit(base, radix, u128::MAX)
+    }
+
+    /// Decode `base`, failing with `RadixErr::Overflow` if its value
+    /// exceeds `limit`.
+    fn radix_x_to_limit(base: &str, radix: usize, limit: u128)
+        -> RadixResult<u128> {
+        let multiplier: u128 = radix as u128;
+        Self::horner(base, radix, limit, |value, digit| {
+            value.checked_mul(multiplier)
+                .and_then(|value| value.checked_add(digit))
+                .filter(|&value| value <= limit)
+        })
+    }
+
+    /// Accumulate the digits of `base`, most significant first, by `step`,
+    /// which returns `None` once the value exceeds `limit`.
+    fn horner<F>(base: &str, radix: usize, limit: u128, step: F)
+        -> RadixResult<u128>
+    where F: Fn(u128, u128) -> Option<u128> {
         Self::validate_radix(radix)?;
-        let base: String = Self::validate_base(base, radix)?;
-        let mut return_val: usize = 0;
-
-        #[inline(always)]
-        fn digit_to_dec(digit: char) -> Result<usize, RadixErr> {
-            match digit {
-                '0'..='9' => Ok(digit as usize - '0' as u8 as usize),
-                'A'..='Z' => Ok(digit as usize - 55),
-                c => Err(RadixErr::IllegalChar(c)),
-            }
+        let digits: String = Self::validate_base(base, radix)?;
+        let mut value: u128 = 0;
+        for token in digits.chars() {
+            let digit: u128 = char_to_digit(token)? as u128;
+            value = step(value, digit).ok_or_else(|| RadixErr::Overflow {
+                input: base.to_string(),
+                radix,
+                limit,
+            })?;
+            debug!("[horner] digit: {}  value: {}", digit, value);
         }
+        Ok(value)
+    }
+}
 
-        debug!("\n");
-        debug!("[radix_x_to_dec] input radix: {}", radix);
-        debug!("[radix_x_to_dec] input base: {}", base);
-        debug!("[radix_x_to_dec] return val: {:?}", return_val);
-        debug!("[radix_x_to_dec] for loop:");
-        for (idx, token) in base.chars().rev().enumerate() {
-            let digit: char = token
-                .to_uppercase()
-                .nth(0)
-                .ok_or(RadixErr::FailedToUppercase)?;
-            let dec_value: usize = digit_to_dec(digit)? * radix.pow(idx as u32);
-            return_val += dec_value;
-            debug!("[radix_x_to_dec]   idx: {:?}", idx);
-            debug!("[radix_x_to_dec]   digit: {:?}  ({}u8)", digit, digit as u8);
-            debug!("[radix_x_to_dec]   decimal value: {}", dec_value);
-            debug!("[radix_x_to_dec]   return val: {:?}", return_val);
-        }
+impl fmt::Display for RadixNum {
+    /// Write the digits with a radix marker: `0b` for radix 2, `0` for
+    /// radix 8, `0x` for radix 16, and an `rN` suffix otherwise. The
+    /// alternate flag, i.e. `{:#}`, leaves the marker out. Width, fill,
+    /// alignment, `+` and zero padding are honored.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let affix = match f.alternate() {
+            true => format::AffixStyle::Bare,
+            false => format::AffixStyle::Native,
+        };
+        let options = format::FormatOptions::new().with_affix(affix);
+        fmt::Display::fmt(&self.display_with(&options), f)
+    }
+}
 
-        Ok(return_val)
+impl fmt::Binary for RadixNum {
+    /// Format the value in binary, whatever radix it is stored in.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_integral(f, 2, "0b", false)
     }
 }
 
-impl fmt::Display for RadixNum {
+impl fmt::Octal for RadixNum {
+    /// Format the value in octal, whatever radix it is stored in.
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        match self.radix() {
-            2  => write!(f, "0b{}", self.as_str()),
-            8  => write!(f, "0{}",  self.as_str()),
-            16 => write!(f, "0x{}", self.as_str()),
-            _ => write!(f, "{}r{}", self.as_str(), self.radix())
-        }
+        self.fmt_integral(f, 8, "0o", false)
+    }
+}
+
+impl fmt::LowerHex for RadixNum {
+    /// Format the value in lowercase hexadecimal, whatever radix it is
+    /// stored in.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_integral(f, 16, "0x", false)
+    }
+}
+
+impl fmt::UpperHex for RadixNum {
+    /// Format the value in uppercase hexadecimal, whatever radix it is
+    /// stored in.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.fmt_integral(f, 16, "0x", true)
+    }
+}
+
+impl RadixNum {
+    /// Write the value in radix `to` like the integer formatting traits
+    /// do, converting the digits directly so that any value fits.
+    fn fmt_integral(
+        &self,
+        f: &mut fmt::Formatter,
+        to: usize,
+        prefix: &str,
+        upper: bool,
+    ) -> fmt::Result {
+        // The digits are validated when `self` is constructed.
+        let digits: Vec<usize> = self.digits()
+            .map(|c| c.to_digit(36).unwrap_or(0) as usize)
+            .collect();
+        let converted: String = convert_digits(&digits, self.radix(), to)
+            .into_iter()
+            .filter_map(|digit| std::char::from_digit(digit as u32, 36))
+            .map(|c| if upper { c.to_ascii_uppercase() } else { c })
+            .collect();
+        f.pad_integral(true, prefix, &converted)
     }
 }
 
 impl From<usize> for RadixNum {
-    fn from(decimal: usize) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: usize) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
 }
 
 impl From<u8> for RadixNum {
-    fn from(decimal: u8) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: u8) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
 }
 
 impl From<u16> for RadixNum {
-    fn from(decimal: u16) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: u16) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
 }
 
 impl From<u32> for RadixNum {
-    fn from(decimal: u32) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: u32) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
 }
 
 impl From<u64> for RadixNum {
-    fn from(decimal: u64) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: u64) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
 }
 
 impl From<u128> for RadixNum {
-    fn from(decimal: u128) -> RadixNum { RadixNum::Radix10(decimal.to_string()) }
+    fn from(decimal: u128) -> RadixNum {
+        RadixNum { repr: Repr::Radix10(decimal.to_string()), width: None }
+    }
+}
+
+impl str::FromStr for RadixNum {
+    type Err = RadixErr;
+
+    /// Parse a number whose radix is given by a prefix or suffix, as
+    /// `RadixNum::parse_auto` detects it, e.g. `"0xFF".parse()`. A literal
+    /// with invalid digits fails with its own error, even one that
+    /// `parse_auto` reads as bare, e.g. `0beef`. A bare digit string is read
+    /// in radix 10.
+    fn from_str(string: &str) -> RadixResult<Self> {
+        match detect::parse_literal(string) {
+            Some(literal) => literal,
+            None => RadixNum::from_str(string, 10),
+        }
+    }
+}
+
+impl<'a> TryFrom<&'a str> for RadixNum {
+    type Error = RadixErr;
+
+    /// Parse a number like `str::parse` does.
+    fn try_from(string: &'a str) -> RadixResult<Self> { string.parse() }
+}
+
+impl PartialEq for RadixNum {
+    fn eq(&self, other: &RadixNum) -> bool {
+        self.cmp(other) == cmp::Ordering::Equal
+    }
+}
+
+impl Eq for RadixNum {}
+
+impl PartialOrd for RadixNum {
+    fn partial_cmp(&self, other: &RadixNum) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for RadixNum {
+    /// Order by radix, then by value, so that the width takes no part.
+    fn cmp(&self, other: &RadixNum) -> cmp::Ordering {
+        fn significant(num: &RadixNum) -> &str {
+            num.as_str().trim_start_matches('0')
+        }
+        let (a, b) = (significant(self), significant(other));
+        // Without leading zeros, more digits means a larger value.
+        self.radix().cmp(&other.radix())
+            .then_with(|| a.len().cmp(&b.len()))
+            .then_with(|| a.cmp(b))
+    }
+}
+
+impl AsRef<str> for RadixNum {
+    fn as_ref(&self) -> &str { self.as_str() }
+}
+
+impl From<RadixNum> for String {
+    fn from(num: RadixNum) -> String { num.as_str().to_string() }
+}
+
+impl PartialEq<u64> for RadixNum {
+    /// Compare the value, whatever the radix.
+    fn eq(&self, other: &u64) -> bool {
+        self.as_u128() == Ok(*other as u128)
+    }
+}
+
+impl<'a> PartialEq<&'a str> for RadixNum {
+    /// Compare the digits, whatever the radix.
+    fn eq(&self, other: &&'a str) -> bool { self.as_str() == *other }
 }
 
 
 
 // Helper functions
 
+/// The number of digits in radix `to` that holds any `width` digits in
+/// radix `from`.
+fn scale_width(width: usize, from: usize, to: usize) -> usize {
+    // The exponent `p` for which `base^p == radix`, if any.
+    let log = |radix: usize, base: usize| {
+        let (mut power, mut exponent) = (1, 0);
+        while power < radix { power *= base; exponent += 1; }
+        Some(exponent).filter(|_| power == radix)
+    };
+    // Powers of a common base line up exactly, e.g. 1 hex digit is 4 bits,
+    // which a floating point logarithm may overestimate.
+    for base in 2 ..= from.min(to) {
+        if let (Some(p), Some(q)) = (log(from, base), log(to, base)) {
+            return (width * p).div_ceil(q);
+        }
+    }
+    let digits: f64 = width as f64 * (from as f64).ln() / (to as f64).ln();
+    digits.ceil() as usize
+}
+
 #[inline(always)]
-fn modulus(a: usize, b: usize) -> usize {
+fn modulus(a: u128, b: u128) -> u128 {
     ((a % b) + b) % b
 }
 
+/// Extract the digits of `number`, least significant first, where the
+/// digit at position `i` is in radix `radix_at(i)`. Zero has no digits.
+fn mixed_radix_digits<F>(number: u128, radix_at: F) -> Vec<usize>
+where F: Fn(usize) -> u128 {
+    let mut number: u128 = number;
+    let mut digits: Vec<usize> = vec![];
+    while number > 0 {
+        let radix: u128 = radix_at(digits.len());
+        digits.push(modulus(number, radix) as usize);
+        number /= radix;
+    }
+    digits
+}
+
+/// Convert the `digits` of a number in radix `from`, most significant
+/// first, to radix `to` by repeated long division, so that the number may
+/// exceed any integer type.
+fn convert_digits(digits: &[usize], from: usize, to: usize) -> Vec<usize> {
+    let mut number: Vec<usize> = digits.iter()
+        .copied()
+        .skip_while(|&digit| digit == 0)
+        .collect();
+    let mut converted: Vec<usize> = vec![];
+    while !number.is_empty() {
+        let mut quotient: Vec<usize> = Vec::with_capacity(number.len());
+        let mut remainder: usize = 0;
+        for &digit in &number {
+            let value: usize = remainder * from + digit;
+            if !quotient.is_empty() || value >= to {
+                quotient.push(value / to);
+            }
+            remainder = value % to;
+        }
+        converted.push(remainder);
+        number = quotient;
+    }
+    if converted.is_empty() { converted.push(0); }
+    converted.reverse();
+    converted
+}
+
+/// Compute the number whose `digits`, least significant first, are in
+/// radix `radix_at(i)` at position `i`.
+fn mixed_radix_value<F>(digits: &[usize], radix_at: F) -> RadixResult<u128>
+where F: Fn(usize) -> u128 {
+    let mut number: u128 = 0;
+    for (idx, &digit) in digits.iter().enumerate().rev() {
+        let radix: u128 = radix_at(idx);
+        if digit as u128 >= radix {
+            return Err(RadixErr::ValueOutOfRange(digit as u128));
+        }
+        number = number.checked_mul(radix)
+            .and_then(|number| number.checked_add(digit as u128))
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(number)
+}
+
+/// Map a `digit` to its character, i.e. `0..=9` to `'0'..='9'` and
+/// `10..=35` to `'A'..='Z'`.
+#[inline(always)]
+fn digit_to_char(digit: usize) -> RadixResult<char> {
+    match digit {
+        0 ..= 9 => Ok((b'0' + digit as u8) as char),     //  1 => '1',  etc
+        10 ..= 35 => Ok((55 + digit as u8) as char),     // 10 => 'A',  etc
+        d => Err(RadixErr::IllegalDigit(d)),
+    }
+}
+
+/// Map an uppercase digit character to its value, i.e. `'0'..='9'` to
+/// `0..=9` and `'A'..='Z'` to `10..=35`.
+#[inline(always)]
+fn char_to_digit(digit: char) -> RadixResult<usize> {
+    match digit {
+        '0'..='9' => Ok(digit as usize - b'0' as usize),
+        'A'..='Z' => Ok(digit as usize - 55),
+        c => Err(RadixErr::IllegalChar(c)),
+    }
+}
+
 
 
 #[cfg(test)]
+#[allow(clippy::unnecessary_cast)]
 mod tests {
     use crate::*;
 
@@ -1714,6 +2205,217 @@ mod tests {
         assert_eq!(Ok(46597557513433), num6.as_decimal());
     }
 
+    #[test]
+    fn conversion_traits() -> RadixResult<()> {
+        let num: RadixNum = "0xFF".parse()?;
+        assert_eq!(RadixNum::from_str("FF", 16)?, num);
+        assert_eq!(RadixNum::from_str("377", 8)?, "0377".parse::<RadixNum>()?);
+        assert_eq!(RadixNum::from_str("255", 10)?, "255".parse::<RadixNum>()?);
+        assert_eq!(RadixNum::from_str("ZZ", 36)?, RadixNum::try_from("ZZr36")?);
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "-1".to_string(),
+            position: 0,
+            digits: vec!['-'],
+            radix: 10,
+        }), "-1".parse::<RadixNum>());
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "0x1G".to_string(),
+            position: 3,
+            digits: vec!['G'],
+            radix: 16,
+        }), "0x1G".parse::<RadixNum>());
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "0beef".to_string(),
+            position: 2,
+            digits: vec!['e', 'e', 'f'],
+            radix: 2,
+        }), "0beef".parse::<RadixNum>());
+        assert_eq!(Err(RadixErr::EmptyInput), "0x".parse::<RadixNum>());
+        assert_eq!("FF", num.as_ref() as &str);
+        assert_eq!("FF", String::from(num.clone()));
+        assert!(num == 255_u64);
+        assert!(num == "FF");
+        assert!(num != 254_u64);
+        assert!(num != "ff");
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_digits() {
+        let err = RadixNum::from_str(" 1X2Y", 16).unwrap_err();
+        assert_eq!(RadixErr::InvalidDigit {
+            input: " 1X2Y".to_string(),
+            position: 2,
+            digits: vec!['X', 'Y'],
+            radix: 16,
+        }, err);
+        let message = "Invalid digits 'X', 'Y' for radix 16 at byte 2";
+        assert_eq!(format!("{}\n   1X2Y\n    ^", message), err.to_string());
+        let err = RadixNum::from_str("1é", 10).unwrap_err();
+        assert_eq!("Invalid digit 'é' for radix 10 at byte 1\n  1é\n   ^",
+                   err.to_string());
+        match RadixNum::from_str("12z4", 10) {
+            Err(RadixErr::InvalidDigit { position: 2, digits, .. }) =>
+                assert_eq!(vec!['z'], digits),
+            other => panic!("expected an invalid digit, got {:?}", other),
+        }
+        for &position in &[1, 9] {
+            let err = RadixErr::InvalidDigit {
+                input: "é".to_string(),
+                position,
+                digits: vec!['é'],
+                radix: 10,
+            };
+            assert!(err.to_string().ends_with("^"));
+        }
+    }
+
+    #[test]
+    fn strict_digits() {
+        assert!(RadixNum::from_str("9", 2).is_err());
+        assert!(RadixNum::from_str("2", 2).is_err());
+        assert!(RadixNum::from_str("A", 10).is_err());
+        assert!(RadixNum::from_str("G", 16).is_err());
+        assert!(RadixNum::from_str("8", 8).is_err());
+        assert_eq!(Ok("11"), RadixNum::from_str("11", 2).as_ref()
+                   .map(RadixNum::as_str));
+        assert_eq!(Ok("ZZ"), RadixNum::from_str("zz", 36).as_ref()
+                   .map(RadixNum::as_str));
+        let err = RadixNum::from_str("1029", 2).unwrap_err();
+        assert_eq!(RadixErr::InvalidDigit {
+            input: "1029".to_string(),
+            position: 2,
+            digits: vec!['2', '9'],
+            radix: 2,
+        }, err);
+    }
+
+    /// `from_str(s, r)` succeeds iff every char of `s` is a digit in `r`,
+    /// for pseudo-random `s` drawn from a fixed seed.
+    #[test]
+    fn strict_digits_property() {
+        let alphabet: Vec<char> = ('0' ..= '9')
+            .chain('A' ..= 'Z')
+            .chain('a' ..= 'z')
+            .chain("-_.+#/é٣７ｆ".chars())
+            .collect();
+        let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+        let mut next = move || {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for _ in 0 .. 10_000 {
+            let radix: usize = 2 + (next() % 35) as usize;
+            let len: usize = 1 + (next() % 8) as usize;
+            let input: String = (0 .. len)
+                .map(|_| alphabet[(next() % alphabet.len() as u64) as usize])
+                .collect();
+            let expected: bool = input.chars().all(|c| {
+                script::normalize_digit(c).is_digit(radix as u32)
+            });
+            assert_eq!(expected, RadixNum::from_str(&input, radix).is_ok(),
+                       "{:?} in radix {}", input, radix);
+        }
+    }
+
+    #[test]
+    fn width() -> RadixResult<()> {
+        let num = RadixNum::from_str("00FF", 16)?;
+        assert_eq!("00FF", num.as_str());
+        assert_eq!(Some(4), num.width());
+        assert_eq!(None, RadixNum::from_str("FF", 16)?.width());
+        assert_eq!(None, RadixNum::from_str("0", 16)?.width());
+        let binary = num.with_radix(2)?;
+        assert_eq!("0000000011111111", binary.as_str());
+        assert_eq!(Some(16), binary.width());
+        assert_eq!(num, binary.with_radix(16)?);
+        let address = RadixNum::from_str("10000000", 16)?.with_width(8)?;
+        assert_eq!("10000000", address.as_str());
+        assert_eq!(32, address.with_radix(2)?.as_str().len());
+        assert_eq!(11, address.with_radix(8)?.as_str().len());
+        assert_eq!(10, address.with_radix(10)?.as_str().len());
+        assert_eq!("00255", num.with_radix(10)?.as_str());
+        assert_eq!("FF", num.with_width(2)?.as_str());
+        assert_eq!(Err(RadixErr::WidthExceeded { width: 1, radix: 16 }),
+                   num.with_width(1));
+        assert_eq!(Err(RadixErr::WidthNotSupported(0)), num.with_width(0));
+        assert_eq!("0000", RadixNum::from(0_u8).with_width(4)?.as_str());
+        assert_eq!(RadixNum::from_str("FF", 16)?, num.without_width());
+        assert_eq!(Ok(255), num.as_decimal());
+        Ok(())
+    }
+
+    #[test]
+    fn width_roundtrip() -> RadixResult<()> {
+        let hex = RadixNum::from_str("0000ABCD", 16)?;
+        let mut num = hex.clone();
+        for _ in 0 .. 5 {
+            let decimal = num.with_radix(10)?;
+            assert_eq!("0000043981", decimal.as_str());
+            assert_eq!(Some(10), decimal.width());
+            num = decimal.with_radix(16)?;
+            assert_eq!("0000ABCD", num.as_str());
+            assert_eq!(Some(8), num.width());
+        }
+        assert_eq!(hex, num);
+        let binary = hex.with_radix(10)?.with_radix(2)?;
+        assert_eq!(Some(32), binary.width());
+        Ok(())
+    }
+
+    #[test]
+    fn width_comparisons() -> RadixResult<()> {
+        let padded = RadixNum::from_str("007", 8)?;
+        assert_eq!(RadixNum::from_str("7", 8)?, padded);
+        assert_ne!(RadixNum::from_str("7", 8)?.as_str(), padded.as_str());
+        assert_eq!(cmp::Ordering::Equal, RadixNum::from_str("00FF", 16)?
+                   .cmp(&RadixNum::from_str("FF", 16)?));
+        assert!(RadixNum::from_str("0A", 16)? > RadixNum::from_str("9", 16)?);
+        assert!(RadixNum::from_str("7", 8)? < RadixNum::from_str("7", 9)?);
+        assert!(RadixNum::from_str("10", 16)? > RadixNum::from_str("9", 16)?);
+        let mut nums: Vec<RadixNum> = ["100", "20", "003"].iter()
+            .map(|digits| RadixNum::from_str(digits, 16))
+            .collect::<RadixResult<Vec<RadixNum>>>()?;
+        nums.sort();
+        let sorted: Vec<&str> = nums.iter().map(RadixNum::as_str).collect();
+        assert_eq!(vec!["003", "20", "100"], sorted);
+        Ok(())
+    }
+
+    #[test]
+    fn overflow() -> RadixResult<()> {
+        let max = RadixNum::from(u128::MAX);
+        assert_eq!(Ok(u128::MAX), max.with_radix(36)?.as_u128());
+        let digits: String = "F".repeat(33);
+        let big = RadixNum::from_str(&digits, 16)?;
+        assert_eq!(33, big.as_str().len());
+        assert_eq!(Err(RadixErr::Overflow {
+            input: digits.clone(),
+            radix: 16,
+            limit: u128::MAX,
+        }), big.as_u128());
+        assert_eq!(Err(RadixErr::Overflow {
+            input: digits,
+            radix: 16,
+            limit: usize::MAX as u128,
+        }), big.as_decimal());
+        let binary = big.with_radix(2)?;
+        assert_eq!("1".repeat(132), binary.as_str());
+        assert_eq!(big, binary.with_radix(16)?);
+        assert_eq!(Err(RadixErr::RadixNotSupported(37)), big.with_radix(37));
+        assert_eq!(Ok(u128::MAX), big.as_u128_wrapping());
+        assert_eq!(Ok(u128::MAX), big.as_u128_saturating());
+        assert_eq!(Ok(usize::MAX), big.as_decimal_saturating());
+        let wide = RadixNum::from_str("10000000000000000F", 16)?;
+        assert_eq!(Ok(0xF), wide.as_decimal_wrapping());
+        assert_eq!(Ok(1 << 68 | 0xF), wide.as_u128_wrapping());
+        assert_eq!(RadixNum::from_str("7", 8)?, RadixNum::from_str("007", 8)?);
+        Ok(())
+    }
+
 }
 
 //  LocalWords:  radix
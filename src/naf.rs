@@ -0,0 +1,176 @@
+//! Non-adjacent form (NAF) and width-w NAF recodings, i.e. signed binary
+//! digits where no two nonzero digits are adjacent, as used for scalar
+//! multiplication on elliptic curves. Digits are least significant first.
+
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// The number of digits of `naf_constant_time`, enough for any `u128`.
+pub const NAF_LEN: usize = 129;
+
+/// Compute the NAF of `number`, i.e. its digits in `{-1, 0, 1}`.
+pub fn naf(number: u128) -> Vec<i8> {
+    recode(number, 2)
+}
+
+/// Compute the width-w NAF of `number`, with `width` in `2..=8`. Nonzero
+/// digits are odd and below `2^(width - 1)` in absolute value, and of any
+/// `width` consecutive digits at most one is nonzero.
+pub fn wnaf(number: u128, width: u32) -> RadixResult<Vec<i8>> {
+    if !(2 ..= 8).contains(&width) {
+        return Err(RadixErr::WidthNotSupported(width as usize));
+    }
+    Ok(recode(number, width))
+}
+
+/// Compute the width-w NAF of the value of `num`. A `width` of 2 yields
+/// the plain NAF.
+pub fn from_radix(num: &RadixNum, width: u32) -> RadixResult<Vec<i8>> {
+    wnaf(num.as_u128()?, width)
+}
+
+/// Evaluate signed binary `digits`, which must add up to a `u128`.
+pub fn from_naf(digits: &[i8]) -> RadixResult<u128> {
+    // The value is kept in sign-magnitude form. Adding the digit before
+    // doubling keeps every intermediate value below the final one.
+    let mut acc: (bool, u128) = (false, 0);
+    for &digit in digits.iter().rev() {
+        let digit: (bool, u128) = (digit < 0, digit.unsigned_abs() as u128);
+        acc = add(add(acc, digit)?, acc)?;
+    }
+    match acc {
+        (true, magnitude) if magnitude != 0 =>
+            Err(RadixErr::ArithmeticOverflow),
+        (_, magnitude) => Ok(magnitude),
+    }
+}
+
+/// Evaluate signed binary `digits`, encoded in a certain `radix`.
+pub fn to_radix(digits: &[i8], radix: usize) -> RadixResult<RadixNum> {
+    RadixNum::from_decimal(from_naf(digits)?, radix)
+}
+
+/// The number of nonzero digits.
+pub fn hamming_weight(digits: &[i8]) -> usize {
+    digits.iter().filter(|&&digit| digit != 0).count()
+}
+
+/// Compute the NAF of `number` in time that does not depend on its value,
+/// for use with secret scalars. The result always has `NAF_LEN` digits,
+/// padded with zeros.
+///
+/// Digit `i` is bit `i + 1` of `3 * number` minus bit `i + 1` of `number`.
+pub fn naf_constant_time(number: u128) -> [i8; NAF_LEN] {
+    // `3 * number` needs 130 bits, so it is kept in two words.
+    let (low, carry) = number.overflowing_add(number << 1);
+    let high: u128 = (number >> 127) + carry as u128;
+    let bit = |(high, low): (u128, u128), idx: usize| -> i8 {
+        let word: u128 =
+            if idx < 128 { low >> idx } else { high >> (idx - 128) };
+        (word & 1) as i8
+    };
+    let mut digits: [i8; NAF_LEN] = [0; NAF_LEN];
+    for (idx, digit) in digits.iter_mut().enumerate() {
+        *digit = bit((high, low), idx + 1) - bit((0, number), idx + 1);
+    }
+    digits
+}
+
+fn recode(number: u128, width: u32) -> Vec<i8> {
+    let modulus: i16 = 1 << width;
+    // Subtracting a negative digit may carry into bit 128.
+    let (mut number, mut high): (u128, bool) = (number, false);
+    let mut digits: Vec<i8> = vec![];
+    while number != 0 || high {
+        let mut digit: i16 = 0;
+        if number & 1 == 1 {
+            digit = (number % modulus as u128) as i16;
+            if digit >= modulus / 2 { digit -= modulus; }
+            if digit < 0 {
+                let (sum, carry) = number.overflowing_add(-digit as u128);
+                number = sum;
+                high |= carry;
+            } else {
+                number -= digit as u128;
+            }
+        }
+        digits.push(digit as i8);
+        number = (number >> 1) | ((high as u128) << 127);
+        high = false;
+    }
+    digits
+}
+
+/// Add two numbers in sign-magnitude form.
+fn add(a: (bool, u128), b: (bool, u128)) -> RadixResult<(bool, u128)> {
+    match (a, b) {
+        ((sign, a), (other, b)) if sign == other => a.checked_add(b)
+            .map(|sum| (sign, sum))
+            .ok_or(RadixErr::ArithmeticOverflow),
+        ((sign, a), (_, b)) if a >= b => Ok((sign, a - b)),
+        ((_, a), (sign, b)) => Ok((sign, b - a)),
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::naf::*;
+
+    const SAMPLES: [u128; 8] = [
+        0, 1, 7, 255, 0xDEAD_BEEF, u64::MAX as u128, u128::MAX - 1, u128::MAX,
+    ];
+
+    #[test]
+    fn plain() -> RadixResult<()> {
+        assert_eq!(vec![-1, 0, 0, 1], naf(7));
+        assert_eq!(vec![-1, 0, -1, 0, 1], naf(11));
+        assert_eq!(Vec::<i8>::new(), naf(0));
+        assert_eq!(2, hamming_weight(&naf(7)));
+        assert_eq!(NAF_LEN, naf(u128::MAX).len());
+        for &n in SAMPLES.iter() {
+            let digits = naf(n);
+            assert_eq!(n, from_naf(&digits)?);
+            assert!(digits.windows(2).all(|pair| pair[0] == 0 || pair[1] == 0));
+        }
+        assert_eq!(Err(RadixErr::ArithmeticOverflow), from_naf(&[-1]));
+        Ok(())
+    }
+
+    #[test]
+    fn windowed() -> RadixResult<()> {
+        assert_eq!(vec![7], wnaf(7, 4)?);
+        assert_eq!(vec![-3, 0, 0, 0, 1], wnaf(13, 3)?);
+        assert_eq!(Err(RadixErr::WidthNotSupported(9)), wnaf(1, 9));
+        for width in 2 ..= 8 {
+            for &n in SAMPLES.iter() {
+                let digits = wnaf(n, width)?;
+                assert_eq!(n, from_naf(&digits)?);
+                for window in digits.windows(width as usize) {
+                    assert!(hamming_weight(window) <= 1);
+                }
+                assert!(digits.iter().all(|&d| d == 0 || d % 2 != 0));
+                let bound: i16 = 1 << (width - 1);
+                assert!(digits.iter().all(|&d| (d as i16).abs() < bound));
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn constant_time() {
+        for &n in SAMPLES.iter() {
+            let mut digits = naf(n);
+            digits.resize(NAF_LEN, 0);
+            assert_eq!(&digits[..], &naf_constant_time(n)[..]);
+        }
+    }
+
+    #[test]
+    fn radix_conversion() -> RadixResult<()> {
+        let num = RadixNum::from_str("DEADBEEF", 16)?;
+        let digits = from_radix(&num, 5)?;
+        assert_eq!("DEADBEEF", to_radix(&digits, 16)?.as_str());
+        Ok(())
+    }
+}
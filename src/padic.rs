@@ -0,0 +1,226 @@
+//! p-adic expansions of integers and rationals, e.g. `-1 = ...4444` in
+//! the 5-adic numbers. The expansion of a rational is eventually periodic,
+//! and its periodic part is detected exactly, within a limit of digits.
+
+use crate::{digit_to_char, RadixErr, RadixResult};
+use std::collections::HashMap;
+use std::fmt;
+
+/// The p-adic expansion of a rational number `p^valuation * u`, where `u`
+/// is a p-adic unit. The digits of `u`, least significant first, are the
+/// `preperiod` followed by the `period` repeated forever. If the period was
+/// not found within the digits computed, it is unknown and left empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PAdic {
+    prime: usize,
+    valuation: i64,
+    preperiod: Vec<usize>,
+    period: Vec<usize>,
+}
+
+/// Compute the `prime`-adic expansion of `numer / denom`, detecting the
+/// period within the first `max_digits` digits of the unit part. The
+/// period can be as long as `denom`, so a longer one is left unknown. At
+/// least the digits up to the radix point are computed.
+pub fn expand(
+    numer: i64,
+    denom: i64,
+    prime: usize,
+    max_digits: usize,
+) -> RadixResult<PAdic> {
+    if !is_prime(prime) || prime > 36 {
+        return Err(RadixErr::RadixNotSupported(prime));
+    }
+    if denom == 0 { return Err(RadixErr::NotFinite); }
+    if numer == 0 {
+        let period: Vec<usize> = vec![0];
+        return Ok(PAdic { prime, valuation: 0, preperiod: vec![], period });
+    }
+    let sign: i128 = if (numer < 0) != (denom < 0) { -1 } else { 1 };
+    let (mut numer, mut denom) = ((numer as i128).abs(), (denom as i128).abs());
+    let p: i128 = prime as i128;
+    let mut valuation: i64 = 0;
+    while numer % p == 0 { numer /= p; valuation += 1; }
+    while denom % p == 0 { denom /= p; valuation -= 1; }
+    let inverse: i128 = mod_inverse(denom % p, p);
+
+    // Each step maps `numer / denom` to `(numer / denom - digit) / p`, so
+    // the digits repeat as soon as a numerator does.
+    let mut numer: i128 = sign * numer;
+    let max_digits: usize = max_digits.max((1 - valuation).max(0) as usize);
+    let mut seen: HashMap<i128, usize> = HashMap::new();
+    let mut digits: Vec<usize> = vec![];
+    while !seen.contains_key(&numer) {
+        if digits.len() == max_digits {
+            let preperiod: Vec<usize> = digits;
+            return Ok(PAdic { prime, valuation, preperiod, period: vec![] });
+        }
+        seen.insert(numer, digits.len());
+        let digit: i128 = (numer * inverse).rem_euclid(p);
+        digits.push(digit as usize);
+        numer = (numer - digit * denom) / p;
+    }
+    let period: Vec<usize> = digits.split_off(seen[&numer]);
+    Ok(PAdic { prime, valuation, preperiod: digits, period })
+}
+
+impl PAdic {
+    pub fn prime(&self) -> usize { self.prime }
+
+    /// The exponent of the largest power of the prime that divides the
+    /// number, negative if it divides the denominator.
+    pub fn valuation(&self) -> i64 { self.valuation }
+
+    /// The non-repeating digits of the unit part, least significant first.
+    pub fn preperiod(&self) -> &[usize] { &self.preperiod }
+
+    /// The repeating digits of the unit part, least significant first, or
+    /// nothing if the period is unknown.
+    pub fn period(&self) -> &[usize] { &self.period }
+
+    /// The coefficient of `p^exponent` in the expansion, if known.
+    pub fn digit(&self, exponent: i64) -> Option<usize> {
+        if exponent < self.valuation { return Some(0); }
+        let idx = (exponent - self.valuation) as usize;
+        match idx.checked_sub(self.preperiod.len()) {
+            None => Some(self.preperiod[idx]),
+            Some(_) if self.period.is_empty() => None,
+            Some(offset) => Some(self.period[offset % self.period.len()]),
+        }
+    }
+
+    /// The first `count` digits, least significant first, starting at the
+    /// coefficient of `p^min(valuation, 0)`. Fewer are returned if the
+    /// period is unknown.
+    pub fn digits(&self, count: usize) -> Vec<usize> {
+        let lowest: i64 = self.valuation.min(0);
+        (0 .. count as i64).map_while(|idx| self.digit(lowest + idx)).collect()
+    }
+
+    /// Render the first `count` digits, most significant first, with the
+    /// same alphabet as `RadixNum`, e.g. `"…44444"` for `-1` in 5-adic.
+    pub fn to_digits_string(&self, count: usize) -> RadixResult<String> {
+        let lowest: i64 = self.valuation.min(0);
+        let mut string: String = String::from("…");
+        for (idx, &digit) in self.digits(count).iter().enumerate().rev() {
+            string.push(digit_to_char(digit)?);
+            if lowest + idx as i64 == 0 && lowest < 0 { string.push('.'); }
+        }
+        Ok(string)
+    }
+}
+
+impl fmt::Display for PAdic {
+    /// Write the expansion with its period in parentheses, e.g. `(13)2`
+    /// for `1/3` in 5-adic, which stands for `…131313132`. An unknown
+    /// period is written as `…`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut preperiod: Vec<usize> = self.preperiod.clone();
+        let mut period: Vec<usize> = self.period.clone();
+        // Unroll the period until the radix point falls in the preperiod.
+        while !period.is_empty()
+            && (preperiod.len() as i64) < -self.valuation {
+            preperiod.push(period[0]);
+            period.rotate_left(1);
+        }
+        let write_digit = |f: &mut fmt::Formatter, digit: usize| {
+            write!(f, "{}", digit_to_char(digit).map_err(|_| fmt::Error)?)
+        };
+        if period.is_empty() {
+            write!(f, "…")?;
+        } else if period != [0] {
+            write!(f, "(")?;
+            for &digit in period.iter().rev() { write_digit(f, digit)?; }
+            write!(f, ")")?;
+        } else if preperiod.len() as i64 <= -self.valuation {
+            write!(f, "0")?;
+        }
+        for (idx, &digit) in preperiod.iter().enumerate().rev() {
+            if idx as i64 == -self.valuation - 1 { write!(f, ".")?; }
+            write_digit(f, digit)?;
+        }
+        for _ in 0 .. self.valuation.max(0) { write!(f, "0")?; }
+        Ok(())
+    }
+}
+
+fn is_prime(n: usize) -> bool {
+    n >= 2 && (2 ..).take_while(|d| d * d <= n).all(|d| !n.is_multiple_of(d))
+}
+
+/// Compute the inverse of `a` modulo the prime `p`, i.e. `a^(p-2) mod p`.
+fn mod_inverse(a: i128, p: i128) -> i128 {
+    (0 .. p - 2).fold(1, |acc, _| acc * a % p)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::padic::*;
+
+    #[test]
+    fn integers() -> RadixResult<()> {
+        let minus_one = expand(-1, 1, 5, 64)?;
+        assert_eq!("…44444", minus_one.to_digits_string(5)?);
+        assert_eq!("(4)",    minus_one.to_string());
+        assert_eq!("22",     expand(12, 1, 5, 64)?.to_string());
+        assert_eq!("100",    expand(25, 1, 5, 64)?.to_string());
+        assert_eq!(2,        expand(25, 1, 5, 64)?.valuation());
+        assert_eq!("(4)32",  expand(-8, 1, 5, 64)?.to_string());
+        assert_eq!("0",      expand(0, 7, 5, 64)?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn rationals() -> RadixResult<()> {
+        let third = expand(1, 3, 5, 64)?;
+        assert_eq!(&[2],    third.preperiod());
+        assert_eq!(&[3, 1], third.period());
+        assert_eq!("(13)2", third.to_string());
+        assert_eq!("…131313132", third.to_digits_string(9)?);
+        assert_eq!("(01)1", expand(1, 3, 2, 64)?.to_string());
+        let tenth = expand(1, 10, 5, 64)?;
+        assert_eq!(-1,         tenth.valuation());
+        assert_eq!("(2).3",    tenth.to_string());
+        assert_eq!("…2222.3",  tenth.to_digits_string(5)?);
+        assert_eq!("0.01",     expand(1, 4, 2, 64)?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn long_periods() -> RadixResult<()> {
+        // The period of 1/1000003 in 2-adic has 1000002 digits.
+        let x = expand(1, 1_000_003, 2, 100)?;
+        assert!(x.period().is_empty());
+        assert_eq!(100, x.preperiod().len());
+        assert_eq!(None, x.digit(100));
+        assert_eq!(100, x.digits(200).len());
+        assert!(x.to_string().starts_with('…'));
+        assert_eq!(x.preperiod()[.. 10], expand(1, 1_000_003, 2, 10)?
+                   .preperiod()[..]);
+        // The digits up to the radix point are always computed.
+        assert_eq!("…1.1", expand(1, 6, 2, 0)?.to_string());
+        Ok(())
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(Err(RadixErr::RadixNotSupported(10)), expand(1, 3, 10, 64));
+        assert_eq!(Err(RadixErr::RadixNotSupported(37)), expand(1, 3, 37, 64));
+        assert_eq!(Err(RadixErr::NotFinite), expand(1, 0, 5, 64));
+    }
+
+    #[test]
+    fn digits_are_consistent() -> RadixResult<()> {
+        // Check that the digits satisfy `x = sum(d_i p^i)` modulo `p^k`.
+        for &(numer, denom, prime) in &[(1, 3, 5), (-7, 9, 2), (22, 7, 3)] {
+            let x = expand(numer, denom, prime, 64)?;
+            let modulus: i128 = (prime as i128).pow(12);
+            let sum: i128 = x.digits(12).iter().rev()
+                .fold(0, |acc, &digit| acc * prime as i128 + digit as i128);
+            assert_eq!(0, (sum * denom as i128 - numer as i128) % modulus);
+        }
+        Ok(())
+    }
+}
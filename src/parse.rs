@@ -0,0 +1,208 @@
+//! Configurable input parsing of a `RadixNum`, e.g. of `DEAD BEEF`,
+//! `1_000_000` or `de:ad:be:ef`, through `RadixNum::from_str_with`.
+
+use crate::script::normalize_digit;
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// The letter case that digits above 9 may be written in.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CaseMode {
+    /// Either case, even mixed, e.g. `DeadBeef`.
+    Insensitive,
+    /// Only upper case, e.g. `DEADBEEF`.
+    Upper,
+    /// Only lower case, e.g. `deadbeef`.
+    Lower,
+}
+
+impl CaseMode {
+    fn accepts(self, digit: char) -> bool {
+        match self {
+            CaseMode::Insensitive => true,
+            CaseMode::Upper => !digit.is_lowercase(),
+            CaseMode::Lower => !digit.is_uppercase(),
+        }
+    }
+}
+
+/// Options for `RadixNum::from_str_with`. The defaults match `from_str`,
+/// i.e. no separators and case insensitive digits.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseOptions {
+    separators: Vec<char>,
+    edge_separators: bool,
+    case: CaseMode,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            separators: Vec::new(),
+            edge_separators: false,
+            case: CaseMode::Insensitive,
+        }
+    }
+}
+
+impl ParseOptions {
+    pub fn new() -> Self { ParseOptions::default() }
+
+    /// Allow `separator` between digits, e.g. `_`, `:` or `-`. A space
+    /// stands for any whitespace. Separators may follow each other. A
+    /// `separator` that could be a digit, in any script, fails with
+    /// `RadixErr::IllegalChar`.
+    pub fn with_separator(self, separator: char) -> RadixResult<Self> {
+        if normalize_digit(separator).is_ascii_alphanumeric() {
+            return Err(RadixErr::IllegalChar(separator));
+        }
+        Ok(self.with_any_separator(separator))
+    }
+
+    /// Like `with_separator`, for a `separator` that the caller has checked
+    /// against the digits of the radix.
+    pub(crate) fn with_any_separator(mut self, separator: char) -> Self {
+        if !self.separators.contains(&separator) {
+            self.separators.push(separator);
+        }
+        self
+    }
+
+    /// Allow separators before the first and after the last digit, e.g.
+    /// `:de:ad:`. They are rejected by default.
+    pub fn with_edge_separators(self, edge_separators: bool) -> Self {
+        ParseOptions { edge_separators, ..self }
+    }
+
+    /// Accept the digits above 9 in a certain `case` only.
+    pub fn with_case(self, case: CaseMode) -> Self {
+        ParseOptions { case, ..self }
+    }
+
+    pub fn separators(&self) -> &[char] { &self.separators }
+
+    pub fn edge_separators(&self) -> bool { self.edge_separators }
+
+    pub fn case(&self) -> CaseMode { self.case }
+
+    pub(crate) fn is_separator(&self, c: char) -> bool {
+        self.separators.iter().any(|&separator| {
+            separator == c || (separator == ' ' && c.is_whitespace())
+        })
+    }
+
+    pub(crate) fn accepts_case(&self, digit: char) -> bool {
+        self.case.accepts(digit)
+    }
+}
+
+impl RadixNum {
+    /// Like `from_str`, but parse `base` according to `options`. A
+    /// separator at either end fails with `RadixErr::IllegalChar` unless
+    /// `options` allow it, and a digit in the wrong case is invalid.
+    pub fn from_str_with(
+        base: &str,
+        radix: usize,
+        options: &ParseOptions,
+    ) -> RadixResult<Self> {
+        Self::validate_radix(radix)?;
+        let base: String = Self::validate_base_with(base, radix, options)?;
+        Self::from_digits(base, radix)
+    }
+
+    /// Like `from_str_with`, for the `digits` of a literal `input`, e.g.
+    /// after its prefix. The `digits` must be a slice of `input`, so that an
+    /// invalid digit is reported at its position in `input`.
+    pub(crate) fn from_str_in(
+        input: &str,
+        digits: &str,
+        radix: usize,
+        options: &ParseOptions,
+    ) -> RadixResult<Self> {
+        let offset: usize = (digits.as_ptr() as usize)
+            .saturating_sub(input.as_ptr() as usize)
+            .min(input.len());
+        Self::from_str_with(digits, radix, options).map_err(|err| match err {
+            RadixErr::InvalidDigit { position, digits, radix, .. } =>
+                RadixErr::InvalidDigit {
+                    input: input.to_string(),
+                    position: offset + position,
+                    digits,
+                    radix,
+                },
+            err => err,
+        })
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::*;
+
+    #[test]
+    fn separators() -> RadixResult<()> {
+        let options = ParseOptions::new()
+            .with_separator('_')?
+            .with_separator(' ')?
+            .with_separator(':')?
+            .with_separator('-')?;
+        let deadbeef = RadixNum::from_str("DEADBEEF", 16)?;
+        assert_eq!(deadbeef,
+                   RadixNum::from_str_with("DEAD BEEF", 16, &options)?);
+        assert_eq!(deadbeef,
+                   RadixNum::from_str_with("de:ad:be:ef", 16, &options)?);
+        assert_eq!(deadbeef,
+                   RadixNum::from_str_with("DE-AD\tBE__EF", 16, &options)?);
+        assert_eq!(Ok(1_000_000),
+                   RadixNum::from_str_with("1_000_000", 10, &options)?
+                   .as_decimal());
+        assert!(RadixNum::from_str("DEAD BEEF", 16).is_err());
+        assert!(RadixNum::from_str_with("DE.AD", 16, &options).is_err());
+        for &digit in &['0', 'a', 'Z', '٣', 'ｆ'] {
+            assert_eq!(Err(RadixErr::IllegalChar(digit)),
+                       ParseOptions::new().with_separator(digit));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn edges() -> RadixResult<()> {
+        let options = ParseOptions::new().with_separator(':')?;
+        assert_eq!(Err(RadixErr::IllegalChar(':')),
+                   RadixNum::from_str_with(":de:ad", 16, &options));
+        assert_eq!(Err(RadixErr::IllegalChar(':')),
+                   RadixNum::from_str_with("de:ad:", 16, &options));
+        let options = options.with_edge_separators(true);
+        assert_eq!("DEAD",
+                   RadixNum::from_str_with(":de:ad:", 16, &options)?.as_str());
+        assert_eq!(Err(RadixErr::EmptyInput),
+                   RadixNum::from_str_with("::", 16, &options));
+        Ok(())
+    }
+
+    #[test]
+    fn case() -> RadixResult<()> {
+        let upper = ParseOptions::new().with_case(CaseMode::Upper);
+        let lower = ParseOptions::new().with_case(CaseMode::Lower);
+        let parse = |base, options| RadixNum::from_str_with(base, 16, options);
+        assert_eq!("BEEF", parse("BEEF", &upper)?.as_str());
+        assert_eq!("BEEF", parse("beef", &lower)?.as_str());
+        assert_eq!("19",   parse("19", &lower)?.as_str());
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "BeEf".to_string(),
+            position: 1,
+            digits: vec!['e', 'f'],
+            radix: 16,
+        }), RadixNum::from_str_with("BeEf", 16, &upper));
+        assert!(RadixNum::from_str_with("Beef", 16, &lower).is_err());
+        let colons = ParseOptions::new().with_separator(':')?;
+        assert_eq!(Err(RadixErr::InvalidDigit {
+            input: "de:xd".to_string(),
+            position: 3,
+            digits: vec!['x'],
+            radix: 16,
+        }), RadixNum::from_str_with("de:xd", 16, &colons));
+        Ok(())
+    }
+}
@@ -0,0 +1,235 @@
+//! Residue number systems: a number is represented by its residues
+//! modulo a set of pairwise coprime moduli, and reconstructed through the
+//! Chinese Remainder Theorem.
+
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// A set of pairwise coprime moduli.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RnsBasis {
+    moduli: Vec<u64>,
+    range: u128,
+}
+
+impl RnsBasis {
+    /// Create a basis from pairwise coprime `moduli`, each at least 2,
+    /// whose product fits in a `u128`.
+    pub fn new(moduli: &[u64]) -> RadixResult<Self> {
+        if moduli.is_empty() { return Err(RadixErr::EmptyInput); }
+        let mut range: u128 = 1;
+        for (idx, &modulus) in moduli.iter().enumerate() {
+            if modulus < 2 {
+                return Err(RadixErr::ValueOutOfRange(modulus as u128));
+            }
+            for &other in &moduli[.. idx] {
+                if gcd(modulus as u128, other as u128) != 1 {
+                    return Err(RadixErr::NotCoprime(other, modulus));
+                }
+            }
+            range = range.checked_mul(modulus as u128)
+                .ok_or(RadixErr::ArithmeticOverflow)?;
+        }
+        Ok(RnsBasis { moduli: moduli.to_vec(), range })
+    }
+
+    /// Create a basis like `new`, which can also represent every value
+    /// below `needed`.
+    pub fn with_range(moduli: &[u64], needed: u128) -> RadixResult<Self> {
+        let basis: RnsBasis = RnsBasis::new(moduli)?;
+        if basis.range < needed {
+            return Err(RadixErr::InsufficientRange {
+                range: basis.range,
+                needed,
+            });
+        }
+        Ok(basis)
+    }
+
+    pub fn moduli(&self) -> &[u64] { &self.moduli }
+
+    /// The dynamic range, i.e. the product of the moduli. Every value below
+    /// it has a unique representation.
+    pub fn range(&self) -> u128 { self.range }
+
+    /// Represent `value` by its residues.
+    pub fn encode(&self, value: u128) -> RadixResult<Rns> {
+        if value >= self.range {
+            return Err(RadixErr::ValueOutOfRange(value));
+        }
+        let residues: Vec<u64> = self.moduli.iter()
+            .map(|&modulus| (value % modulus as u128) as u64)
+            .collect();
+        Ok(Rns { basis: self.clone(), residues })
+    }
+
+    /// Represent the value of `num` by its residues.
+    pub fn from_radix(&self, num: &RadixNum) -> RadixResult<Rns> {
+        self.encode(num.as_u128()?)
+    }
+
+    /// Wrap `residues` that were computed elsewhere.
+    pub fn from_residues(&self, residues: &[u64]) -> RadixResult<Rns> {
+        if residues.len() != self.moduli.len() {
+            return Err(RadixErr::MismatchedModuli);
+        }
+        for (&residue, &modulus) in residues.iter().zip(self.moduli.iter()) {
+            if residue >= modulus {
+                return Err(RadixErr::ValueOutOfRange(residue as u128));
+            }
+        }
+        Ok(Rns { basis: self.clone(), residues: residues.to_vec() })
+    }
+}
+
+/// A number represented by its residues with respect to an `RnsBasis`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rns {
+    basis: RnsBasis,
+    residues: Vec<u64>,
+}
+
+impl Rns {
+    pub fn basis(&self) -> &RnsBasis { &self.basis }
+
+    pub fn residues(&self) -> &[u64] { &self.residues }
+
+    /// Reconstruct the value through the Chinese Remainder Theorem.
+    /// Garner's algorithm is used, so that no intermediate value exceeds
+    /// the dynamic range.
+    pub fn decode(&self) -> RadixResult<u128> {
+        let mut value: u128 = 0;
+        let mut product: u128 = 1;
+        let moduli = self.basis.moduli.iter();
+        for (&residue, &modulus) in self.residues.iter().zip(moduli) {
+            let (residue, modulus) = (residue as u128, modulus as u128);
+            let inverse: u128 = mod_inverse(product % modulus, modulus)?;
+            let diff: u128 = (residue + modulus - value % modulus) % modulus;
+            let coefficient: u128 = diff * inverse % modulus;
+            value = coefficient.checked_mul(product)
+                .and_then(|term| term.checked_add(value))
+                .ok_or(RadixErr::ArithmeticOverflow)?;
+            product = product.checked_mul(modulus)
+                .ok_or(RadixErr::ArithmeticOverflow)?;
+        }
+        Ok(value)
+    }
+
+    /// Reconstruct the value, encoded in a certain `radix`.
+    pub fn to_radix(&self, radix: usize) -> RadixResult<RadixNum> {
+        RadixNum::from_decimal(self.decode()?, radix)
+    }
+
+    /// Add componentwise, modulo the dynamic range.
+    pub fn add(&self, other: &Rns) -> RadixResult<Rns> {
+        self.zip_with(other, |a, b, m| (a + b) % m)
+    }
+
+    /// Subtract componentwise, modulo the dynamic range.
+    pub fn sub(&self, other: &Rns) -> RadixResult<Rns> {
+        self.zip_with(other, |a, b, m| (a + m - b) % m)
+    }
+
+    /// Multiply componentwise, modulo the dynamic range.
+    pub fn mul(&self, other: &Rns) -> RadixResult<Rns> {
+        self.zip_with(other, |a, b, m| a * b % m)
+    }
+
+    fn zip_with<F>(&self, other: &Rns, op: F) -> RadixResult<Rns>
+    where F: Fn(u128, u128, u128) -> u128 {
+        if self.basis != other.basis { return Err(RadixErr::MismatchedModuli); }
+        let residues: Vec<u64> = self.residues.iter()
+            .zip(other.residues.iter())
+            .zip(self.basis.moduli.iter())
+            .map(|((&a, &b), &m)| op(a as u128, b as u128, m as u128) as u64)
+            .collect();
+        Ok(Rns { basis: self.basis.clone(), residues })
+    }
+}
+
+#[inline(always)]
+fn gcd(a: u128, b: u128) -> u128 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// Compute the inverse of `a` modulo `modulus`, with the extended
+/// Euclidean algorithm.
+fn mod_inverse(a: u128, modulus: u128) -> RadixResult<u128> {
+    let (mut old_r, mut r) = (a as i128, modulus as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let quotient: i128 = old_r / r;
+        let next_r = old_r - quotient * r;
+        old_r = r;
+        r = next_r;
+        let next_s = old_s - quotient * s;
+        old_s = s;
+        s = next_s;
+    }
+    if old_r != 1 {
+        return Err(RadixErr::NotCoprime(a as u64, modulus as u64));
+    }
+    Ok(old_s.rem_euclid(modulus as i128) as u128)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::rns::*;
+
+    #[test]
+    fn basis() -> RadixResult<()> {
+        let basis = RnsBasis::new(&[3, 5, 7])?;
+        assert_eq!(105, basis.range());
+        assert_eq!(Err(RadixErr::NotCoprime(6, 9)), RnsBasis::new(&[6, 7, 9]));
+        assert_eq!(Err(RadixErr::ValueOutOfRange(1)), RnsBasis::new(&[1, 7]));
+        assert_eq!(Err(RadixErr::EmptyInput), RnsBasis::new(&[]));
+        assert_eq!(
+            Err(RadixErr::InsufficientRange { range: 105, needed: 256 }),
+            RnsBasis::with_range(&[3, 5, 7], 256)
+        );
+        let max = u64::MAX;
+        assert_eq!(Err(RadixErr::ArithmeticOverflow),
+                   RnsBasis::new(&[max, max - 1, max - 2]));
+        Ok(())
+    }
+
+    #[test]
+    fn crt() -> RadixResult<()> {
+        let basis = RnsBasis::new(&[3, 5, 7])?;
+        let num = basis.encode(23)?;
+        assert_eq!(&[2, 3, 2], num.residues());
+        assert_eq!(23, num.decode()?);
+        assert_eq!(Err(RadixErr::ValueOutOfRange(105)), basis.encode(105));
+        for value in 0 .. basis.range() {
+            assert_eq!(value, basis.encode(value)?.decode()?);
+        }
+        let max = u64::MAX;
+        let wide = RnsBasis::new(&[max, max - 1])?;
+        let value = wide.range() - 1;
+        assert_eq!(value, wide.encode(value)?.decode()?);
+        Ok(())
+    }
+
+    #[test]
+    fn arithmetic() -> RadixResult<()> {
+        let basis = RnsBasis::new(&[7, 11, 13, 17])?;
+        let (a, b) = (basis.encode(1234)?, basis.encode(567)?);
+        assert_eq!(1801,    a.add(&b)?.decode()?);
+        assert_eq!(667,     a.sub(&b)?.decode()?);
+        assert_eq!(1234 * 567 % 17017, a.mul(&b)?.decode()?);
+        assert_eq!(17017 - 667, b.sub(&a)?.decode()?);
+        let other = RnsBasis::new(&[3, 5])?.encode(1)?;
+        assert_eq!(Err(RadixErr::MismatchedModuli), a.add(&other));
+        Ok(())
+    }
+
+    #[test]
+    fn radix_conversion() -> RadixResult<()> {
+        let basis = RnsBasis::new(&[251, 253, 255, 256])?;
+        let num = RadixNum::from_str("DEADBEE", 16)?;
+        let rns = basis.from_radix(&num)?;
+        assert_eq!("DEADBEE", rns.to_radix(16)?.as_str());
+        Ok(())
+    }
+}
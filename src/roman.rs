@@ -0,0 +1,263 @@
+//! Roman numerals, with optional vinculum or apostrophus notation for
+//! values above 3999.
+
+use crate::{RadixErr, RadixResult};
+
+/// The combining overline that marks a vinculum, multiplying by 1000.
+const VINCULUM: char = '\u{305}';
+
+/// The ways in which values above 3999 can be written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RomanStyle {
+    /// Plain numerals, limited to `1..=3999`.
+    Standard,
+    /// An overline multiplies by 1000, e.g. `V̅` is 5000. Up to 3,999,999.
+    Vinculum,
+    /// Apostrophus forms such as `CIↃ` for 1000 and `IↃↃ` for 5000.
+    /// Up to 399,999.
+    Apostrophus,
+}
+
+const NUMERALS: [(u32, &str); 13] = [
+    (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"),
+    ( 100, "C"), ( 90, "XC"), ( 50, "L"), ( 40, "XL"),
+    (  10, "X"), (  9, "IX"), (  5, "V"), (  4, "IV"),
+    (   1, "I"),
+];
+
+const APOSTROPHUS: [(u32, &str); 6] = [
+    (100_000, "CCCIↃↃↃ"), (50_000, "IↃↃↃ"), (10_000, "CCIↃↃ"),
+    (  5_000, "IↃↃ"),     ( 1_000, "CIↃ"),  (   500, "IↃ"),
+];
+
+/// Write `number` as an uppercase Roman numeral in the given `style`.
+pub fn to_roman(number: u32, style: RomanStyle) -> RadixResult<String> {
+    let out_of_range = RadixErr::ValueOutOfRange(number as u128);
+    match style {
+        RomanStyle::Standard if number < 4000 => standard(number),
+        RomanStyle::Vinculum if number < 4000 => standard(number),
+        RomanStyle::Vinculum if number < 4_000_000 => {
+            let thousands: String = standard(number / 1000)?.chars()
+                .flat_map(|c| vec![c, VINCULUM])
+                .collect();
+            let rest: String = match number % 1000 {
+                0 => String::new(),
+                rest => standard(rest)?,
+            };
+            Ok(thousands + &rest)
+        },
+        RomanStyle::Apostrophus if 0 < number && number < 400_000 => {
+            let mut number: u32 = number;
+            let mut numeral: String = String::new();
+            for &(value, symbol) in APOSTROPHUS.iter() {
+                while number >= value {
+                    numeral.push_str(symbol);
+                    number -= value;
+                }
+            }
+            if number > 0 { numeral.push_str(&standard(number)?); }
+            Ok(numeral)
+        },
+        _ => Err(out_of_range),
+    }
+}
+
+/// Write `number` as a lowercase Roman numeral in the given `style`.
+pub fn to_roman_lower(number: u32, style: RomanStyle) -> RadixResult<String> {
+    Ok(to_roman(number, style)?.to_lowercase())
+}
+
+/// Parse a Roman numeral, accepting only the canonical form produced by
+/// `to_roman`, in either case. Forms like `IIII` or `IC` are rejected, with
+/// the first character that deviates from the canonical form.
+pub fn parse_strict(numeral: &str) -> RadixResult<u32> {
+    let numeral: &str = numeral.trim();
+    let tokens: Vec<Token> = tokenize(numeral)?;
+    let number: u32 = evaluate(&tokens)?;
+    let style = if tokens.iter().any(|token| token.apostrophus) {
+        RomanStyle::Apostrophus
+    } else if numeral.contains(VINCULUM) {
+        RomanStyle::Vinculum
+    } else {
+        RomanStyle::Standard
+    };
+    let canonical: String = match to_roman(number, style) {
+        Ok(canonical) => canonical,
+        // The prefixes of a canonical numeral are canonical, so the last
+        // char of the shortest prefix that isn't is the offending one.
+        Err(err) => return Err(numeral.char_indices()
+            .find(|&(idx, c)| {
+                let end: usize = idx + c.len_utf8();
+                end < numeral.len() && parse_strict(&numeral[.. end]).is_err()
+            })
+            .or_else(|| numeral.char_indices().last())
+            .map_or(err, |(_, c)| RadixErr::IllegalChar(c))),
+    };
+    let mut canonical_chars = canonical.chars();
+    for c in numeral.chars() {
+        let expected: Option<char> = canonical_chars.next();
+        if expected != c.to_uppercase().next() {
+            return Err(RadixErr::IllegalChar(c));
+        }
+    }
+    match canonical_chars.next() {
+        None => Ok(number),
+        Some(c) => Err(RadixErr::IllegalChar(c)),
+    }
+}
+
+/// Parse a Roman numeral, accepting non-canonical forms such as `IIII`,
+/// `IC` or `MMMM` as long as every character is a Roman numeral.
+pub fn parse_lenient(numeral: &str) -> RadixResult<u32> {
+    evaluate(&tokenize(numeral.trim())?)
+}
+
+/// Write a `number` in `1..=3999` using plain numerals.
+fn standard(number: u32) -> RadixResult<String> {
+    if number == 0 || number >= 4000 {
+        return Err(RadixErr::ValueOutOfRange(number as u128));
+    }
+    let mut number: u32 = number;
+    let mut numeral: String = String::new();
+    for &(value, symbol) in NUMERALS.iter() {
+        while number >= value {
+            numeral.push_str(symbol);
+            number -= value;
+        }
+    }
+    Ok(numeral)
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Token { value: u32, apostrophus: bool }
+
+fn tokenize(numeral: &str) -> RadixResult<Vec<Token>> {
+    if numeral.is_empty() { return Err(RadixErr::EmptyInput); }
+    let upper: String = numeral.to_uppercase();
+    let chars: Vec<char> = upper.chars().collect();
+    let mut tokens: Vec<Token> = vec![];
+    let mut idx: usize = 0;
+    'outer: while idx < chars.len() {
+        for &(value, symbol) in APOSTROPHUS.iter() {
+            let len: usize = symbol.chars().count();
+            let matches = chars.len() >= idx + len
+                && symbol.chars().eq(chars[idx .. idx + len].iter().cloned());
+            if matches {
+                tokens.push(Token { value, apostrophus: true });
+                idx += len;
+                continue 'outer;
+            }
+        }
+        let value: u32 = match chars[idx] {
+            'I' => 1,
+            'V' => 5,
+            'X' => 10,
+            'L' => 50,
+            'C' => 100,
+            'D' => 500,
+            'M' => 1000,
+            c => return Err(RadixErr::IllegalChar(c)),
+        };
+        idx += 1;
+        if chars.get(idx) == Some(&VINCULUM) {
+            tokens.push(Token { value: value * 1000, apostrophus: false });
+            idx += 1;
+        } else {
+            tokens.push(Token { value, apostrophus: false });
+        }
+    }
+    Ok(tokens)
+}
+
+/// Sum the `tokens`, subtracting each token that precedes a larger one.
+fn evaluate(tokens: &[Token]) -> RadixResult<u32> {
+    let mut number: i64 = 0;
+    for (idx, token) in tokens.iter().enumerate() {
+        let next: u32 = tokens.get(idx + 1).map_or(0, |next| next.value);
+        if token.value < next {
+            number -= token.value as i64;
+        } else {
+            number += token.value as i64;
+        }
+    }
+    if number <= 0 { return Err(RadixErr::ValueOutOfRange(0)); }
+    if number > u32::MAX as i64 { return Err(RadixErr::ArithmeticOverflow); }
+    Ok(number as u32)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::roman::*;
+
+    #[test]
+    fn standard_numerals() -> RadixResult<()> {
+        let roman = |n| to_roman(n, RomanStyle::Standard);
+        assert_eq!("I",         roman(1)?);
+        assert_eq!("IV",        roman(4)?);
+        assert_eq!("XCIX",      roman(99)?);
+        assert_eq!("MCMXCIV",   roman(1994)?);
+        assert_eq!("MMMCMXCIX", roman(3999)?);
+        assert_eq!("mmxxvi",    to_roman_lower(2026, RomanStyle::Standard)?);
+        assert_eq!(Err(RadixErr::ValueOutOfRange(0)),    roman(0));
+        assert_eq!(Err(RadixErr::ValueOutOfRange(4000)), roman(4000));
+        Ok(())
+    }
+
+    #[test]
+    fn large_numerals() -> RadixResult<()> {
+        let vinculum = |n| to_roman(n, RomanStyle::Vinculum);
+        assert_eq!("MMMCMXCIX",   vinculum(3999)?);
+        assert_eq!("I\u{305}V\u{305}", vinculum(4000)?);
+        assert_eq!("V\u{305}I",         vinculum(5001)?);
+        let lower = to_roman_lower(4000, RomanStyle::Vinculum)?;
+        assert_eq!("i\u{305}v\u{305}", lower);
+        let apostrophus = |n| to_roman(n, RomanStyle::Apostrophus);
+        assert_eq!("CIↃ",          apostrophus(1000)?);
+        assert_eq!("IↃↃCIↃCIↃXII", apostrophus(7012)?);
+        assert_eq!("ciↄ", to_roman_lower(1000, RomanStyle::Apostrophus)?);
+        Ok(())
+    }
+
+    #[test]
+    fn strict_parsing() -> RadixResult<()> {
+        assert_eq!(1994,    parse_strict("MCMXCIV")?);
+        assert_eq!(1994,    parse_strict("mcmxciv")?);
+        assert_eq!(4000,    parse_strict("I\u{305}V\u{305}")?);
+        assert_eq!(7012,    parse_strict("IↃↃCIↃCIↃXII")?);
+        assert_eq!(Err(RadixErr::IllegalChar('I')), parse_strict("IIII"));
+        assert_eq!(Err(RadixErr::IllegalChar('I')), parse_strict("IC"));
+        assert_eq!(Err(RadixErr::IllegalChar('X')), parse_strict("VX"));
+        assert_eq!(Err(RadixErr::IllegalChar('A')), parse_strict("MA"));
+        assert_eq!(Err(RadixErr::IllegalChar('M')), parse_strict("MMMM"));
+        assert_eq!(Err(RadixErr::IllegalChar('m')), parse_strict("mmmmcm"));
+        assert_eq!(Err(RadixErr::EmptyInput),       parse_strict(""));
+        Ok(())
+    }
+
+    #[test]
+    fn lenient_parsing() -> RadixResult<()> {
+        assert_eq!(4,    parse_lenient("IIII")?);
+        assert_eq!(99,   parse_lenient("IC")?);
+        assert_eq!(4000, parse_lenient("MMMM")?);
+        assert_eq!(1000, parse_lenient("ciↄ")?);
+        assert_eq!(Err(RadixErr::IllegalChar('Ↄ')), parse_lenient("Ↄ"));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        for n in 1 .. 4000 {
+            assert_eq!(n, parse_strict(&to_roman(n, RomanStyle::Standard)?)?);
+        }
+        for &n in &[4000, 12_345, 999_999, 3_999_999] {
+            assert_eq!(n, parse_strict(&to_roman(n, RomanStyle::Vinculum)?)?);
+        }
+        for &n in &[1000, 12_345, 123_456, 399_999] {
+            let numeral = to_roman(n, RomanStyle::Apostrophus)?;
+            assert_eq!(n, parse_strict(&numeral)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+//! Digits in other scripts than ASCII: any Unicode decimal digit (general
+//! category Nd) is accepted on input, and output can be written in a
+//! chosen script.
+
+use crate::{RadixErr, RadixNum, RadixResult};
+
+/// The zero of every run of ten Unicode decimal digits, as of Unicode 15.0.
+const DIGIT_ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6,
+    0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0,
+    0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900,
+    0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30, 0x11066,
+    0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650, 0x116C0,
+    0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140,
+    0x1E2F0, 0x1E4F0, 0x1E950, 0x1FBF0,
+];
+
+const FULLWIDTH_UPPER_A: u32 = 0xFF21;
+const FULLWIDTH_LOWER_A: u32 = 0xFF41;
+
+/// The scripts in which the digits of a `RadixNum` can be written.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Script {
+    /// ASCII digits and letters, i.e. `RadixNum::as_str`.
+    Latin,
+    /// Fullwidth digits and letters, e.g. `１Ｆ`, for any radix.
+    Fullwidth,
+    ArabicIndic,
+    ExtendedArabicIndic,
+    Devanagari,
+    Bengali,
+    Gurmukhi,
+    Gujarati,
+    Tamil,
+    Telugu,
+    Kannada,
+    Malayalam,
+    Thai,
+    Lao,
+    Tibetan,
+    Myanmar,
+    Khmer,
+    Mongolian,
+}
+
+impl Script {
+    /// The code point of the zero digit of the script.
+    fn zero(self) -> u32 {
+        match self {
+            Script::Latin => 0x0030,
+            Script::Fullwidth => 0xFF10,
+            Script::ArabicIndic => 0x0660,
+            Script::ExtendedArabicIndic => 0x06F0,
+            Script::Devanagari => 0x0966,
+            Script::Bengali => 0x09E6,
+            Script::Gurmukhi => 0x0A66,
+            Script::Gujarati => 0x0AE6,
+            Script::Tamil => 0x0BE6,
+            Script::Telugu => 0x0C66,
+            Script::Kannada => 0x0CE6,
+            Script::Malayalam => 0x0D66,
+            Script::Thai => 0x0E50,
+            Script::Lao => 0x0ED0,
+            Script::Tibetan => 0x0F20,
+            Script::Myanmar => 0x1040,
+            Script::Khmer => 0x17E0,
+            Script::Mongolian => 0x1810,
+        }
+    }
+
+    /// Whether the script has letters for the digits above 9.
+    fn has_letters(self) -> bool {
+        matches!(self, Script::Latin | Script::Fullwidth)
+    }
+}
+
+impl RadixNum {
+    /// Write the digits in a certain `script`. Scripts without letters only
+    /// support radices up to 10.
+    pub fn to_script(&self, script: Script) -> RadixResult<String> {
+        if self.radix() > 10 && !script.has_letters() {
+            return Err(RadixErr::RadixNotSupported(self.radix()));
+        }
+        self.digits().map(|digit| {
+            let code: u32 = match digit {
+                '0' ..= '9' => script.zero() + (digit as u32 - '0' as u32),
+                _ if script == Script::Latin => digit as u32,
+                _ => FULLWIDTH_UPPER_A + (digit as u32 - 'A' as u32),
+            };
+            char::from_u32(code).ok_or(RadixErr::IllegalChar(digit))
+        }).collect()
+    }
+}
+
+/// Map a decimal digit of any script to its ASCII digit, and a fullwidth
+/// letter to its ASCII letter. Other characters are returned unchanged.
+pub(crate) fn normalize_digit(c: char) -> char {
+    let code: u32 = c as u32;
+    if c.is_ascii() { return c; }
+    let ascii = |offset: u32, base: char| {
+        char::from_u32(base as u32 + offset).unwrap_or(c)
+    };
+    match code {
+        _ if (FULLWIDTH_UPPER_A .. FULLWIDTH_UPPER_A + 26).contains(&code) =>
+            ascii(code - FULLWIDTH_UPPER_A, 'A'),
+        _ if (FULLWIDTH_LOWER_A .. FULLWIDTH_LOWER_A + 26).contains(&code) =>
+            ascii(code - FULLWIDTH_LOWER_A, 'a'),
+        _ => {
+            // The zeros are sorted, so the run of `c` is the last zero
+            // that is not greater than it.
+            let idx: usize = DIGIT_ZEROS.partition_point(|&zero| zero <= code);
+            match idx.checked_sub(1).map(|idx| DIGIT_ZEROS[idx]) {
+                Some(zero) if code - zero < 10 => ascii(code - zero, '0'),
+                _ => c,
+            }
+        }
+    }
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::script::*;
+
+    #[test]
+    fn input() -> RadixResult<()> {
+        assert_eq!("42",   RadixNum::from_str("٤٢", 10)?.as_str());
+        assert_eq!("42",   RadixNum::from_str("۴۲", 10)?.as_str());
+        assert_eq!("1024", RadixNum::from_str("१०२४", 10)?.as_str());
+        assert_eq!("99",   RadixNum::from_str("๙๙", 10)?.as_str());
+        assert_eq!("1F",   RadixNum::from_str("１ｆ", 16)?.as_str());
+        assert_eq!("101",  RadixNum::from_str("𝟏𝟎𝟏", 2)?.as_str());
+        assert!(RadixNum::from_str("٤x", 10).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn output() -> RadixResult<()> {
+        let num = RadixNum::from(1024_u32);
+        assert_eq!("١٠٢٤", num.to_script(Script::ArabicIndic)?);
+        assert_eq!("१०२४", num.to_script(Script::Devanagari)?);
+        assert_eq!("๑๐๒๔", num.to_script(Script::Thai)?);
+        assert_eq!("1024", num.to_script(Script::Latin)?);
+        let hex = num.with_radix(16)?;
+        assert_eq!("４００", hex.to_script(Script::Fullwidth)?);
+        let hex = RadixNum::from_str("BEEF", 16)?;
+        assert_eq!("ＢＥＥＦ", hex.to_script(Script::Fullwidth)?);
+        assert_eq!(Err(RadixErr::RadixNotSupported(16)),
+                   hex.to_script(Script::Thai));
+        Ok(())
+    }
+
+    #[test]
+    fn roundtrip() -> RadixResult<()> {
+        let scripts = [
+            Script::ArabicIndic, Script::ExtendedArabicIndic,
+            Script::Devanagari, Script::Bengali, Script::Tamil, Script::Khmer,
+            Script::Mongolian, Script::Fullwidth,
+        ];
+        let num = RadixNum::from(9876543210_u64);
+        for &script in scripts.iter() {
+            let string = num.to_script(script)?;
+            assert_eq!(num, RadixNum::from_str(&string, 10)?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,220 @@
+//! Sexagesimal notation for angles and times, e.g. `12°34'56.7"` in
+//! degrees, minutes and seconds, or `12h34m56.7s` in hours, minutes and
+//! seconds. The seconds carry a decimal fraction.
+
+use crate::{RadixErr, RadixResult};
+
+/// Convert `number` to base 60 digits, most significant first.
+pub fn to_base60(number: u128) -> Vec<u8> {
+    let mut number: u128 = number;
+    let mut digits: Vec<u8> = vec![(number % 60) as u8];
+    while number >= 60 {
+        number /= 60;
+        digits.push((number % 60) as u8);
+    }
+    digits.reverse();
+    digits
+}
+
+/// Convert base 60 `digits`, most significant first, to a number.
+pub fn from_base60(digits: &[u8]) -> RadixResult<u128> {
+    if digits.is_empty() { return Err(RadixErr::EmptyInput); }
+    let mut number: u128 = 0;
+    for &digit in digits {
+        if digit >= 60 { return Err(RadixErr::IllegalDigit(digit as usize)); }
+        number = number.checked_mul(60)
+            .and_then(|number| number.checked_add(digit as u128))
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+    }
+    Ok(number)
+}
+
+/// The format of a sexagesimal angle or time: the separator after each of
+/// the three fields, and the number of decimal places of the seconds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SexagesimalFormat {
+    separators: [String; 3],
+    precision: usize,
+}
+
+impl SexagesimalFormat {
+    /// Degrees, minutes and seconds, e.g. `12°34'56.7"`.
+    pub fn dms() -> Self {
+        SexagesimalFormat::new(["°", "'", "\""])
+    }
+
+    /// Hours, minutes and seconds, e.g. `12h34m56.7s`.
+    pub fn hms() -> Self {
+        SexagesimalFormat::new(["h", "m", "s"])
+    }
+
+    /// Use other separators, e.g. `":"`, `":"` and `""` for `12:34:56.7`.
+    pub fn with_separators(
+        self,
+        first: &str,
+        second: &str,
+        third: &str,
+    ) -> Self {
+        let separators = [first.into(), second.into(), third.into()];
+        SexagesimalFormat { separators, ..self }
+    }
+
+    /// Use `precision` decimal places for the seconds.
+    pub fn with_precision(self, precision: usize) -> Self {
+        SexagesimalFormat { precision, ..self }
+    }
+
+    pub fn separators(&self) -> [&str; 3] {
+        let [first, second, third] = &self.separators;
+        [first, second, third]
+    }
+
+    pub fn precision(&self) -> usize { self.precision }
+
+    /// Format `value`, in degrees or hours. The value is rounded to the
+    /// precision as a whole, so that e.g. `59.99"` carries into the next
+    /// minute rather than rendering as `60.0"`.
+    pub fn format(&self, value: f64) -> RadixResult<String> {
+        if !value.is_finite() { return Err(RadixErr::NotFinite); }
+        let scale: u128 = 10u128.checked_pow(self.precision as u32)
+            .ok_or(RadixErr::ArithmeticOverflow)?;
+        let total: f64 = (value.abs() * 3600.0 * scale as f64).round();
+        if total >= u128::MAX as f64 {
+            return Err(RadixErr::ArithmeticOverflow);
+        }
+        let total: u128 = total as u128;
+        let (seconds, fraction) = (total / scale, total % scale);
+        let [first, second, third] = &self.separators;
+        let mut string: String = String::new();
+        if value < 0.0 && total != 0 { string.push('-'); }
+        string += &format!("{}{}{:02}{}{:02}",
+                           seconds / 3600, first,
+                           seconds / 60 % 60, second,
+                           seconds % 60);
+        if self.precision > 0 {
+            let width: usize = self.precision;
+            string += &format!(".{:0width$}", fraction, width = width);
+        }
+        string += third;
+        Ok(string)
+    }
+
+    /// Parse a value, in degrees or hours. Trailing fields may be omitted,
+    /// e.g. `12°34'` is `12°34'0"`, and a leading `-` negates the value.
+    pub fn parse(&self, input: &str) -> RadixResult<f64> {
+        let input: &str = input.trim();
+        let (negative, mut rest) = match input.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, input.strip_prefix('+').unwrap_or(input)),
+        };
+        if rest.is_empty() { return Err(RadixErr::EmptyInput); }
+        let [first, second, third] = &self.separators;
+        let units: f64 = take_field(&mut rest, first)? as f64;
+        let minutes: u128 = take_field(&mut rest, second)?;
+        let seconds: f64 = take_seconds(&mut rest, third)?;
+        if let Some(c) = rest.chars().next() {
+            return Err(RadixErr::IllegalChar(c));
+        }
+        if minutes >= 60 { return Err(RadixErr::ValueOutOfRange(minutes)); }
+        if seconds >= 60.0 {
+            return Err(RadixErr::ValueOutOfRange(seconds as u128));
+        }
+        let value: f64 = units + minutes as f64 / 60.0 + seconds / 3600.0;
+        Ok(if negative { -value } else { value })
+    }
+
+    fn new(separators: [&str; 3]) -> Self {
+        let [first, second, third] = separators;
+        SexagesimalFormat {
+            separators: [first.into(), second.into(), third.into()],
+            precision: 0,
+        }
+    }
+}
+
+/// Take an integer field, followed by `separator`, off the front of `rest`.
+/// An empty `rest` is a zero field.
+fn take_field(rest: &mut &str, separator: &str) -> RadixResult<u128> {
+    if rest.is_empty() { return Ok(0); }
+    let end: usize = rest.find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (digits, tail) = rest.split_at(end);
+    if digits.is_empty() || !tail.starts_with(separator) {
+        return Err(RadixErr::IllegalChar(tail.chars().next().unwrap_or(' ')));
+    }
+    *rest = &tail[separator.len() ..];
+    digits.parse().map_err(|_| RadixErr::ArithmeticOverflow)
+}
+
+/// Take the seconds, with an optional decimal fraction and an optional
+/// trailing `separator`, off the front of `rest`.
+fn take_seconds(rest: &mut &str, separator: &str) -> RadixResult<f64> {
+    if rest.is_empty() { return Ok(0.0); }
+    let end: usize = rest.find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+    let (digits, tail) = rest.split_at(end);
+    let illegal = || RadixErr::IllegalChar(tail.chars().next().unwrap_or(' '));
+    let seconds: f64 = digits.parse().map_err(|_| illegal())?;
+    *rest = tail.strip_prefix(separator).unwrap_or(tail);
+    Ok(seconds)
+}
+
+
+
+#[cfg(test)]
+mod tests {
+    use crate::sexagesimal::*;
+
+    #[test]
+    fn base60() -> RadixResult<()> {
+        assert_eq!(vec![1, 57, 46, 40], to_base60(424000));
+        assert_eq!(vec![0], to_base60(0));
+        assert_eq!(424000, from_base60(&[1, 57, 46, 40])?);
+        assert_eq!(Err(RadixErr::IllegalDigit(60)), from_base60(&[60]));
+        Ok(())
+    }
+
+    #[test]
+    fn format() -> RadixResult<()> {
+        let dms = SexagesimalFormat::dms().with_precision(1);
+        let value = 12.0 + 34.0 / 60.0 + 56.7 / 3600.0;
+        assert_eq!("12°34'56.7\"", dms.format(value)?);
+        assert_eq!("-12°34'56.7\"", dms.format(-value)?);
+        let hms = SexagesimalFormat::hms().with_precision(1);
+        assert_eq!("12h34m56.7s", hms.format(value)?);
+        let colons = SexagesimalFormat::hms().with_separators(":", ":", "");
+        assert_eq!("12:34:57", colons.format(value)?);
+        assert_eq!("0°00'00\"", SexagesimalFormat::dms().format(0.0)?);
+        assert_eq!(Err(RadixErr::NotFinite), dms.format(f64::NAN));
+        Ok(())
+    }
+
+    #[test]
+    fn carry() -> RadixResult<()> {
+        let dms = SexagesimalFormat::dms().with_precision(1);
+        let value = 12.0 + 59.0 / 60.0 + 59.99 / 3600.0;
+        assert_eq!("13°00'00.0\"", dms.format(value)?);
+        let value = 12.0 + 34.0 / 60.0 + 59.96 / 3600.0;
+        assert_eq!("12°35'00.0\"", dms.format(value)?);
+        assert_eq!("12°34'59.96\"", dms.with_precision(2).format(value)?);
+        Ok(())
+    }
+
+    #[test]
+    fn parse() -> RadixResult<()> {
+        let dms = SexagesimalFormat::dms();
+        let value = 12.0 + 34.0 / 60.0 + 56.7 / 3600.0;
+        assert!((value - dms.parse("12°34'56.7\"")?).abs() < 1e-12);
+        assert!((value + dms.parse("-12°34'56.7")?).abs() < 1e-12);
+        assert_eq!(12.5, dms.parse("12°30'")?);
+        assert_eq!(12.0, dms.parse("12°")?);
+        let hms = SexagesimalFormat::hms();
+        assert!((value - hms.parse("12h34m56.7s")?).abs() < 1e-12);
+        assert_eq!(Err(RadixErr::ValueOutOfRange(60)),
+                   dms.parse("12°60'00\""));
+        assert_eq!(Err(RadixErr::IllegalChar('x')), dms.parse("12x34'"));
+        assert_eq!(Err(RadixErr::IllegalChar('!')), dms.parse("12°34'56\"!"));
+        assert_eq!(Err(RadixErr::EmptyInput), dms.parse("-"));
+        Ok(())
+    }
+}